@@ -5,9 +5,8 @@ extern crate strum;
 
 use strum::{AsStaticRef, IntoEnumIterator};
 
-//use libswe_sys::sweconst::{Bodies, Calandar, HouseSystem};
 use libswe_sys::sweconst::{
-    Angle, Bodies, Calandar, House, Object, ObjectType, OptionalFlag,
+    Bodies, Calandar, CalcFlags, HouseSystem, Object, ObjectType, OptionalFlag,
 };
 use libswe_sys::swerust::{
     handler_swe02, handler_swe03, handler_swe07, handler_swe08, handler_swe14,
@@ -38,10 +37,13 @@ fn main() {
     //    env::var("CARGO_MANIFEST_DIR").unwrap() + swe02_path_final;
     let swe02_path: &str =
         "/Users/stephanebressani/Code/Rust/libswe-sys/src/swisseph/sweph";
-    handler_swe02::set_ephe_path(&swe02_path);
+    handler_swe02::set_ephe_path(swe02_path).unwrap();
     println!("Set the path of ephemeris to: {}", &swe02_path);
-    println!("Version swephem: {}", handler_swe02::version());
-    println!("Get path of library: {}", handler_swe02::get_library_path());
+    println!("Version swephem: {}", handler_swe02::version().unwrap());
+    println!(
+        "Get path of library: {}",
+        handler_swe02::get_library_path().unwrap()
+    );
 
     const PATH: &str = "examples/data.json";
     let mut s = String::new();
@@ -64,20 +66,25 @@ fn main() {
     println!("Get julday: {:?}", julday);
 
     let mut object: Vec<Object> = Vec::new();
-    let mut calc: handler_swe03::CalcUtResult;
     for bodies in Bodies::iter() {
-        if bodies.clone().object_type() == ObjectType::PlanetOrStar
-            || bodies.clone().object_type() == ObjectType::Fiction
+        if bodies.object_type() == ObjectType::PlanetOrStar
+            || bodies.object_type() == ObjectType::Fiction
         {
-            calc = handler_swe03::calc_ut(
+            let calc = match handler_swe03::calc_ut(
                 julday,
-                bodies.clone(),
-                OptionalFlag::Speed as i32,
-            );
+                bodies,
+                OptionalFlag::Speed.into(),
+            ) {
+                Ok(calc) => calc,
+                Err(err) => {
+                    println!("{}: {}", bodies.as_static(), err);
+                    continue;
+                },
+            };
             object.push(Object::new(
-                bodies.clone(),
-                bodies.clone().as_static(),
-                bodies.clone().object_type(),
+                bodies,
+                bodies.as_static(),
+                bodies.object_type(),
                 calc.longitude,
                 calc.latitude,
                 calc.speed_longitude,
@@ -92,12 +99,12 @@ fn main() {
     let pheno_ut: handler_swe07::PhenoUtResult = handler_swe07::pheno_ut(
         julday,
         Bodies::Sun,
-        OptionalFlag::Speed as i32,
-    );
+        OptionalFlag::Speed.into(),
+    )
+    .unwrap();
     println!("PhenoUt: {:?}", pheno_ut);
 
-    // let hsys = HouseSystem::Placidus;
-    let name = handler_swe14::house_name('P');
+    let name = handler_swe14::house_name(HouseSystem::Placidus).unwrap();
     println!("Hsys: {}", name);
 
     let utc_time_zone: handler_swe08::UtcTimeZoneResult =
@@ -120,80 +127,47 @@ fn main() {
         utc_time_zone.min[1],
         utc_time_zone.sec[1],*/
         Calandar::Gregorian,
-    );
+    )
+    .unwrap();
     println!("utc_to_jd: {:?}", utc_to_jd);
 
     // Whole signs
-    let result_w =
-        handler_swe14::houses(utc_to_jd.julian_day_ut, data.lat, data.lng, 'W');
-    //println!("House object: {:?}", result);
-    let mut house2: Vec<House> = Vec::new();
-    for (i, res) in result_w.clone().cusps.iter().enumerate() {
-        if i > 0 {
-            // No angle calculation when Nothing
-            let angle = Angle::Nothing;
-            house2.push(House::new(i as i32, res.clone(), angle));
-            if i + 1 > 12 {
-                break;
-            }
-        }
-    }
-
-    for h in house2 {
+    let result_w = handler_swe14::houses(
+        utc_to_jd.julian_day_ut,
+        data.lat,
+        data.lng,
+        HouseSystem::WholeSign,
+        CalcFlags::new(),
+    )
+    .unwrap();
+    for h in result_w.houses() {
         println!("{:?}", h);
     }
-    println!("House (wohle signs): {:?}", result_w.clone());
-
-    // Wohle Signs
-    let result =
-        handler_swe14::houses(utc_to_jd.julian_day_ut, data.lat, data.lng, 'P');
-    //println!("House object: {:?}", result);
-    let mut house: Vec<House> = Vec::new();
-    for (i, res) in result.clone().cusps.iter().enumerate() {
-        if i > 0 {
-            let angle;
-            /*
-            if result.clone().ascmc[0] == res.clone() {
-                angle = Angle::Asc;
-            }
-            if result.clone().ascmc[1] == res.clone() {
-                angle = Angle::Fc;
-            }
-            if result.clone().ascmc[2] == res.clone() {
-                angle = Angle::Desc;
-            }
-            if result.clone().ascmc[3] == res.clone() {
-                angle = Angle::Mc;
-            }*/
-            // This is tested with Placidus only
-            // the line above ascmc[?] don't work for Desc and Mc
-            angle = match i {
-                1 => Angle::Asc,
-                4 => Angle::Fc,
-                7 => Angle::Desc,
-                10 => Angle::Mc,
-                _ => Angle::Nothing,
-            };
-            house.push(House::new(i as i32, res.clone(), angle));
-            if i + 1 > 12 {
-                break;
-            }
-        }
-    }
+    println!("House (wohle signs): {:?}", result_w);
 
-    for h in house {
+    // Placidus
+    let result = handler_swe14::houses(
+        utc_to_jd.julian_day_ut,
+        data.lat,
+        data.lng,
+        HouseSystem::Placidus,
+        CalcFlags::new(),
+    )
+    .unwrap();
+    for h in result.houses() {
         println!("{:?}", h);
     }
-    println!("House (Placidus): {:?}", result.clone());
+    println!("House (Placidus): {:?}", result);
 
     // Fortuna part
     let calcfp = handler_swe03::calc_ut_fp(
         julday,
         data.lat,
         data.lng,
-        'P',
-        OptionalFlag::Speed as i32,
-    );
+        HouseSystem::Placidus,
+        OptionalFlag::Speed.into(),
+    )
+    .unwrap();
     println!("Fortuna Part: {}", calcfp.longitude);
 
     println!("Exit and free memory swephem");
@@ -3,4 +3,5 @@ extern crate strum;
 extern crate strum_macros;
 mod raw;
 pub mod sweconst;
+pub mod sweerror;
 pub mod swerust;
@@ -1,5 +1,5 @@
 use std::os::raw::{c_char, c_double, c_int};
-/// Interface https://www.astro.com/ftp/swisseph/doc/swephprg.htm#_Toc19111156
+// Interface https://www.astro.com/ftp/swisseph/doc/swephprg.htm#_Toc19111156
 #[link(name = "swe")]
 extern "C" {
 
@@ -68,6 +68,112 @@ extern "C" {
         serr: *mut c_char,
     ) -> c_int;
 
+    /// int swe_calc(
+    ///     double tjd_et,
+    ///     int ipl,
+    ///     int iflag,
+    ///     double* xx,
+    ///     char* serr);
+    ///
+    /// tjd_et    = Julian day, Ephemeris time, where tjd_et = tjd_ut +
+    ///             swe_deltat(tjd_ut)
+    pub fn swe_calc(
+        tjd_et: c_double,
+        ipl: c_int,
+        iflag: c_int,
+        xx: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /*
+     * 5. Fixed stars functions
+     */
+
+    /// int32 swe_fixstar2_ut(
+    ///     char *star,
+    ///     double tjd_ut,
+    ///     int32 iflag,
+    ///     double *xx,
+    ///     char *serr);
+    ///
+    /// star      = name of fixed star to be searched, returned name of found
+    ///             star (twice SE_MAX_STNAME)
+    pub fn swe_fixstar2_ut(
+        star: *mut c_char,
+        tjd_ut: c_double,
+        iflag: c_int,
+        xx: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_fixstar2_mag(
+    ///     char *star,
+    ///     double* mag,
+    ///     char* serr);
+    pub fn swe_fixstar2_mag(
+        star: *mut c_char,
+        mag: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /*
+     * 6. Kepler elements, apsides and nodes, orbital periods
+     */
+
+    /// int32 swe_nod_aps_ut(
+    ///     double tjd_ut,
+    ///     int32 ipl,
+    ///     int32 iflag,
+    ///     int32 method,
+    ///     double *xnasc,       /* array of 6 doubles for ascending node */
+    ///     double *xndsc,       /* array of 6 doubles for descending node */
+    ///     double *xperi,       /* array of 6 doubles for perihelion */
+    ///     double *xaphe,       /* array of 6 doubles for aphelion */
+    ///     char *serr);
+    pub fn swe_nod_aps_ut(
+        tjd_ut: c_double,
+        ipl: c_int,
+        iflag: c_int,
+        method: c_int,
+        xnasc: *mut c_double,
+        xndsc: *mut c_double,
+        xperi: *mut c_double,
+        xaphe: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_get_orbital_elements(
+    ///     double tjd_et,
+    ///     int32 ipl,
+    ///     int32 iflag,
+    ///     double *dret,        /* array of return values, declare as dret[50] */
+    ///     char *serr);
+    pub fn swe_get_orbital_elements(
+        tjd_et: c_double,
+        ipl: c_int,
+        iflag: c_int,
+        dret: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_orbit_max_min_true_distance(
+    ///     double tjd_et,
+    ///     int32 ipl,
+    ///     int32 iflag,         /* ephemeris flag and optional SEFLG_HELCTR */
+    ///     double *dmax,        /* maximum distance */
+    ///     double *dmin,        /* minimum distance */
+    ///     double *dtrue,       /* true distance */
+    ///     char *serr);
+    pub fn swe_orbit_max_min_true_distance(
+        tjd_et: c_double,
+        ipl: c_int,
+        iflag: c_int,
+        dmax: *mut c_double,
+        dmin: *mut c_double,
+        dtrue: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
     /*
      * 7. Eclipses, risings, settings, meridian transits, planetary phenomena
      */
@@ -86,6 +192,343 @@ extern "C" {
         serr: *mut c_char,
     ) -> c_int;
 
+    /// int32 swe_sol_eclipse_when_glob(
+    ///     double tjd_start,    /* start date for search, Jul. day UT */
+    ///     int32 ifl,           /* ephemeris flag */
+    ///     int32 ifltype,       /* eclipse type wanted: SE_ECL_TOTAL etc. */
+    ///     double *tret,        /* return array, 10 doubles, see below */
+    ///     AS_BOOL backward,    /* TRUE, if backward search */
+    ///     char *serr);         /* return error string */
+    pub fn swe_sol_eclipse_when_glob(
+        tjd_start: c_double,
+        ifl: c_int,
+        ifltype: c_int,
+        tret: *mut c_double,
+        backward: c_int,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_sol_eclipse_when_loc(
+    ///     double tjd_start,    /* start date for search, Jul. day UT */
+    ///     int32 ifl,           /* ephemeris flag */
+    ///     double *geopos,      /* 3 doubles for geo. lon, lat, height */
+    ///     double *tret,        /* return array, 10 doubles, see below */
+    ///     double *attr,        /* return array, 20 doubles, see below */
+    ///     AS_BOOL backward,    /* TRUE, if backward search */
+    ///     char *serr);         /* return error string */
+    pub fn swe_sol_eclipse_when_loc(
+        tjd_start: c_double,
+        ifl: c_int,
+        geopos: *mut c_double,
+        tret: *mut c_double,
+        attr: *mut c_double,
+        backward: c_int,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_sol_eclipse_how(
+    ///     double tjd_ut,       /* time, Jul. day UT */
+    ///     int32 ifl,           /* ephemeris flag */
+    ///     double *geopos,      /* geogr. longitude, latitude, height */
+    ///     double *attr,        /* return array, 20 doubles, see below */
+    ///     char *serr);         /* return error string */
+    pub fn swe_sol_eclipse_how(
+        tjd_ut: c_double,
+        ifl: c_int,
+        geopos: *mut c_double,
+        attr: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_sol_eclipse_where(
+    ///     double tjd_ut,       /* time, Jul. day UT */
+    ///     int32 ifl,           /* ephemeris flag */
+    ///     double *geopos,      /* return array, 2 doubles, geo. long. and lat. */
+    ///     double *attr,        /* return array, 20 doubles, see below */
+    ///     char *serr);         /* return error string */
+    pub fn swe_sol_eclipse_where(
+        tjd_ut: c_double,
+        ifl: c_int,
+        geopos: *mut c_double,
+        attr: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_lun_eclipse_when(
+    ///     double tjd_start,    /* start date for search, Jul. day UT */
+    ///     int32 ifl,           /* ephemeris flag */
+    ///     int32 ifltype,       /* eclipse type wanted: SE_ECL_TOTAL etc. */
+    ///     double *tret,        /* return array, 10 doubles, see below */
+    ///     AS_BOOL backward,    /* TRUE, if backward search */
+    ///     char *serr);         /* return error string */
+    pub fn swe_lun_eclipse_when(
+        tjd_start: c_double,
+        ifl: c_int,
+        ifltype: c_int,
+        tret: *mut c_double,
+        backward: c_int,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_lun_eclipse_when_loc(
+    ///     double tjd_start,    /* start date for search, Jul. day UT */
+    ///     int32 ifl,           /* ephemeris flag */
+    ///     double *geopos,      /* 3 doubles for geo. lon, lat, height */
+    ///     double *tret,        /* return array, 10 doubles, see below */
+    ///     double *attr,        /* return array, 20 doubles, see below */
+    ///     AS_BOOL backward,    /* TRUE, if backward search */
+    ///     char *serr);         /* return error string */
+    pub fn swe_lun_eclipse_when_loc(
+        tjd_start: c_double,
+        ifl: c_int,
+        geopos: *mut c_double,
+        tret: *mut c_double,
+        attr: *mut c_double,
+        backward: c_int,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_lun_eclipse_how(
+    ///     double tjd_ut,       /* time, Jul. day UT */
+    ///     int32 ifl,           /* ephemeris flag */
+    ///     double *geopos,      /* input array, geopos, geolon, geoheight */
+    ///     double *attr,        /* return array, 20 doubles, see below */
+    ///     char *serr);         /* return error string */
+    pub fn swe_lun_eclipse_how(
+        tjd_ut: c_double,
+        ifl: c_int,
+        geopos: *mut c_double,
+        attr: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_lun_occult_when_glob(
+    ///     double tjd_start,    /* start date for search, Jul. day UT */
+    ///     int32 ipl,           /* planet number */
+    ///     char* starname,      /* star name, must be NULL or "" if not a star */
+    ///     int32 ifl,           /* ephemeris flag */
+    ///     int32 ifltype,       /* eclipse type wanted */
+    ///     double *tret,        /* return array, 10 doubles, see below */
+    ///     AS_BOOL backward,    /* TRUE, if backward search */
+    ///     char *serr);         /* return error string */
+    pub fn swe_lun_occult_when_glob(
+        tjd_start: c_double,
+        ipl: c_int,
+        starname: *mut c_char,
+        ifl: c_int,
+        ifltype: c_int,
+        tret: *mut c_double,
+        backward: c_int,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_lun_occult_when_loc(
+    ///     double tjd_start,    /* start date for search, Jul. day UT */
+    ///     int32 ipl,           /* planet number */
+    ///     char* starname,      /* star name, must be NULL or "" if not a star */
+    ///     int32 ifl,           /* ephemeris flag */
+    ///     double *geopos,      /* 3 doubles for geo. lon, lat, height */
+    ///     double *tret,        /* return array, 10 doubles, see below */
+    ///     double *attr,        /* return array, 20 doubles, see below */
+    ///     AS_BOOL backward,    /* TRUE, if backward search */
+    ///     char *serr);         /* return error string */
+    pub fn swe_lun_occult_when_loc(
+        tjd_start: c_double,
+        ipl: c_int,
+        starname: *mut c_char,
+        ifl: c_int,
+        geopos: *mut c_double,
+        tret: *mut c_double,
+        attr: *mut c_double,
+        backward: c_int,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_lun_occult_where(
+    ///     double tjd_ut,       /* time, Jul. day UT */
+    ///     int32 ipl,           /* planet number */
+    ///     char* starname,      /* star name, must be NULL or "" if not a star */
+    ///     int32 ifl,           /* ephemeris flag */
+    ///     double *geopos,      /* return array, 2 doubles, geo. long. and lat. */
+    ///     double *attr,        /* return array, 20 doubles, see below */
+    ///     char *serr);         /* return error string */
+    pub fn swe_lun_occult_where(
+        tjd_ut: c_double,
+        ipl: c_int,
+        starname: *mut c_char,
+        ifl: c_int,
+        geopos: *mut c_double,
+        attr: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_rise_trans(
+    ///     double tjd_ut,       /* search after this time (UT) */
+    ///     int32 ipl,           /* planet number, if planet or moon */
+    ///     char *starname,      /* star name, if star */
+    ///     int32 epheflag,      /* ephemeris flag */
+    ///     int32 rsmi,          /* rise, set, or one of the two transits */
+    ///     double *geopos,      /* geograph. long., lat., height */
+    ///     double atpress,      /* atmospheric pressure in mbar/hPa */
+    ///     double attemp,       /* atmospheric temperature in deg. C */
+    ///     double *tret,        /* return address for rise time etc. */
+    ///     char *serr);         /* return address for error message */
+    pub fn swe_rise_trans(
+        tjd_ut: c_double,
+        ipl: c_int,
+        starname: *mut c_char,
+        epheflag: c_int,
+        rsmi: c_int,
+        geopos: *mut c_double,
+        atpress: c_double,
+        attemp: c_double,
+        tret: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_rise_trans_true_hor(
+    ///     double tjd_ut,       /* search after this time (UT) */
+    ///     int32 ipl,           /* planet number, if planet or moon */
+    ///     char *starname,      /* star name, if star */
+    ///     int32 epheflag,      /* ephemeris flag */
+    ///     int32 rsmi,          /* rise, set, or one of the two transits */
+    ///     double *geopos,      /* geograph. long., lat., height */
+    ///     double atpress,      /* atmospheric pressure in mbar/hPa */
+    ///     double attemp,       /* atmospheric temperature in deg. C */
+    ///     double horhgt,       /* height of local horizon in deg */
+    ///     double *tret,        /* return address for rise time etc. */
+    ///     char *serr);         /* return address for error message */
+    pub fn swe_rise_trans_true_hor(
+        tjd_ut: c_double,
+        ipl: c_int,
+        starname: *mut c_char,
+        epheflag: c_int,
+        rsmi: c_int,
+        geopos: *mut c_double,
+        atpress: c_double,
+        attemp: c_double,
+        horhgt: c_double,
+        tret: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// void swe_azalt(
+    ///     double tjd_ut,       // UT
+    ///     int32 calc_flag,     // SE_ECL2HOR or SE_EQU2HOR
+    ///     double *geopos,      // array of 3 doubles: geo. long., lat., height
+    ///     double atpress,      // atmospheric pressure in mbar (hPa)
+    ///     double attemp,       // atmospheric temperature in degrees Celsius
+    ///     double *xin,         // array of 3 doubles: position of body
+    ///     double *xaz);        // return array of 3 doubles
+    pub fn swe_azalt(
+        tjd_ut: c_double,
+        calc_flag: c_int,
+        geopos: *mut c_double,
+        atpress: c_double,
+        attemp: c_double,
+        xin: *mut c_double,
+        xaz: *mut c_double,
+    );
+
+    /// void swe_azalt_rev(
+    ///     double tjd_ut,
+    ///     int32 calc_flag,     /* either SE_HOR2ECL or SE_HOR2EQU */
+    ///     double *geopos,      /* array of 3 doubles for geo. pos. */
+    ///     double *xin,         /* azimuth and true altitude */
+    ///     double *xout);       /* ecliptic or equatorial coordinates */
+    pub fn swe_azalt_rev(
+        tjd_ut: c_double,
+        calc_flag: c_int,
+        geopos: *mut c_double,
+        xin: *mut c_double,
+        xout: *mut c_double,
+    );
+
+    /// double swe_refrac_extended(
+    ///     double inalt,        /* altitude of object in degrees */
+    ///     double geoalt,       /* altitude of observer in meters */
+    ///     double atpress,      /* atmospheric pressure in mbar (hPa) */
+    ///     double attemp,       /* atmospheric temperature in deg. C */
+    ///     double lapse_rate,   /* (dattemp/dgeoalt) = [deg K/m] */
+    ///     int32 calc_flag,     /* SE_TRUE_TO_APP or SE_APP_TO_TRUE */
+    ///     double *dret);       /* array of 4 doubles; declare 20 ! */
+    pub fn swe_refrac_extended(
+        inalt: c_double,
+        geoalt: c_double,
+        atpress: c_double,
+        attemp: c_double,
+        lapse_rate: c_double,
+        calc_flag: c_int,
+        dret: *mut c_double,
+    ) -> c_double;
+
+    /// void swe_set_lapse_rate(double lapse_rate);
+    pub fn swe_set_lapse_rate(lapse_rate: c_double);
+
+    /// int32 swe_heliacal_ut(
+    ///     double tjdstart,     /* Julian day number of start date */
+    ///     double *dgeo,        /* geographic position */
+    ///     double *datm,        /* atmospheric conditions */
+    ///     double *dobs,        /* observer description */
+    ///     char *objectname,    /* name string of fixed star or planet */
+    ///     int32 event_type,    /* event type */
+    ///     int32 helflag,       /* calculation flag, bitmap */
+    ///     double *dret,        /* result: array of at least 50 doubles */
+    ///     char * serr);        /* error string */
+    pub fn swe_heliacal_ut(
+        tjdstart: c_double,
+        dgeo: *mut c_double,
+        datm: *mut c_double,
+        dobs: *mut c_double,
+        objectname: *mut c_char,
+        event_type: c_int,
+        helflag: c_int,
+        dret: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_heliacal_pheno_ut(
+    ///     double tjd_ut,       /* Julian day number */
+    ///     double *dgeo,        /* geographic position */
+    ///     double *datm,        /* atmospheric conditions */
+    ///     double *dobs,        /* observer description */
+    ///     char *objectname,    /* name string of fixed star or planet */
+    ///     int32 event_type,    /* event type */
+    ///     int32 helflag,       /* calculation flag, bitmap */
+    ///     double *darr,        /* return array, 50 doubles */
+    ///     char * serr);        /* error string */
+    pub fn swe_heliacal_pheno_ut(
+        tjd_ut: c_double,
+        dgeo: *mut c_double,
+        datm: *mut c_double,
+        dobs: *mut c_double,
+        objectname: *mut c_char,
+        event_type: c_int,
+        helflag: c_int,
+        darr: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_vis_limit_mag(
+    ///     double tjdut,        /* Julian day number */
+    ///     double *dgeo,        /* geographic position */
+    ///     double *datm,        /* atmospheric conditions */
+    ///     double *dobs,        /* observer description */
+    ///     char *objectname,    /* name string of fixed star or planet */
+    ///     int32 helflag,       /* calculation flag, bitmap */
+    ///     double *dret,        /* result: magnitude required to be visible */
+    ///     char * serr);        /* error string */
+    pub fn swe_vis_limit_mag(
+        tjdut: c_double,
+        dgeo: *mut c_double,
+        datm: *mut c_double,
+        dobs: *mut c_double,
+        objectname: *mut c_char,
+        helflag: c_int,
+        dret: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
     /*
      * 8. Date and time conversion functions
      */
@@ -146,6 +589,193 @@ extern "C" {
         serr: *mut c_char,
     ) -> c_int;
 
+    /// int swe_date_conversion(
+    ///     int y, int m, int d,  /* year, month, day */
+    ///     double utime,         /* universal time in hours (decimal) */
+    ///     char c,               /* calendar g[regorian]|j[ulian] */
+    ///     double *tjd);
+    pub fn swe_date_conversion(
+        y: c_int,
+        m: c_int,
+        d: c_int,
+        utime: c_double,
+        c: c_char,
+        tjd: *mut c_double,
+    ) -> c_int;
+
+    /// void swe_revjul(
+    ///     double tjd,       /* Julian day number */
+    ///     int gregflag,     /* Gregorian calendar: 1, Julian calendar: 0 */
+    ///     int *year,        /* target addresses for year, etc. */
+    ///     int *month,
+    ///     int *day,
+    ///     double *hour);
+    pub fn swe_revjul(
+        tjd: c_double,
+        gregflag: c_int,
+        year: *mut c_int,
+        month: *mut c_int,
+        day: *mut c_int,
+        hour: *mut c_double,
+    );
+
+    /// void swe_jdet_to_utc(
+    ///     double tjd_et,    /* Julian day number in ET (TT) */
+    ///     int32 gregflag,   /* Gregorian calendar: 1, Julian calendar: 0 */
+    ///     int32 *iyear, int32 *imonth, int32 *iday,
+    ///     int32 *ihour, int32 *imin, double *dsec);  /* NOTE: second is a
+    ///                                                   decimal */
+    pub fn swe_jdet_to_utc(
+        tjd_et: c_double,
+        gregflag: c_int,
+        iyear: *mut c_int,
+        imonth: *mut c_int,
+        iday: *mut c_int,
+        ihour: *mut c_int,
+        imin: *mut c_int,
+        dsec: *mut c_double,
+    );
+
+    /// void swe_jdut1_to_utc(
+    ///     double tjd_ut,    /* Julian day number in UT (UT1) */
+    ///     int32 gregflag,   /* Gregorian calendar: 1, Julian calendar: 0 */
+    ///     int32 *iyear, int32 *imonth, int32 *iday,
+    ///     int32 *ihour, int32 *imin, double *dsec);  /* NOTE: second is a
+    ///                                                   decimal */
+    pub fn swe_jdut1_to_utc(
+        tjd_ut: c_double,
+        gregflag: c_int,
+        iyear: *mut c_int,
+        imonth: *mut c_int,
+        iday: *mut c_int,
+        ihour: *mut c_int,
+        imin: *mut c_int,
+        dsec: *mut c_double,
+    );
+
+    /// int swe_day_of_week(
+    ///     double jd);       /* Monday = 0, ... Sunday = 6 */
+    pub fn swe_day_of_week(jd: c_double) -> c_int;
+
+    /// int swe_time_equ(
+    ///     double tjd,       /* Julian day number in UT */
+    ///     double *e,        /* E = LAT - LMT, in days */
+    ///     char *serr);
+    pub fn swe_time_equ(
+        tjd: c_double,
+        e: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_lmt_to_lat(
+    ///     double tjd_lmt,   /* Julian day number in Local Mean Time */
+    ///     double geolon,    /* eastern longitudes are positive */
+    ///     double *tjd_lat,  /* Julian day number in Local Apparent Time */
+    ///     char *serr);
+    pub fn swe_lmt_to_lat(
+        tjd_lmt: c_double,
+        geolon: c_double,
+        tjd_lat: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_lat_to_lmt(
+    ///     double tjd_lat,   /* Julian day number in Local Apparent Time */
+    ///     double geolon,    /* eastern longitudes are positive */
+    ///     double *tjd_lmt,  /* Julian day number in Local Mean Time */
+    ///     char *serr);
+    pub fn swe_lat_to_lmt(
+        tjd_lat: c_double,
+        geolon: c_double,
+        tjd_lmt: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /*
+     * 9. Delta T-related functions
+     */
+
+    /// double swe_deltat(
+    ///     double tjd);
+    pub fn swe_deltat(tjd: c_double) -> c_double;
+
+    /// double swe_deltat_ex(
+    ///     double tjd,
+    ///     int32 ephe,   /* ephemeris flag (one of SEFLG_SWIEPH,
+    ///                      SEFLG_JPLEPH, SEFLG_MOSEPH) */
+    ///     char *serr);  /* error string or warning */
+    pub fn swe_deltat_ex(
+        tjd: c_double,
+        ephe: c_int,
+        serr: *mut c_char,
+    ) -> c_double;
+
+    /// /* set a user defined delta t to be returned by functions
+    /// swe_deltat() and swe_deltat_ex() */
+    /// void swe_set_delta_t_userdef(
+    ///     double t_acc);
+    pub fn swe_set_delta_t_userdef(dt: c_double);
+
+    /// /* get tidal acceleration used in swe_deltat() */
+    /// double swe_get_tid_acc(
+    ///     void);
+    pub fn swe_get_tid_acc() -> c_double;
+
+    /// /* set tidal acceleration to be used in swe_deltat() */
+    /// void swe_set_tid_acc(
+    ///     double t_acc);
+    pub fn swe_set_tid_acc(t_acc: c_double);
+
+    /*
+     * 10. The function swe_set_topo() for topocentric planet positions
+     */
+
+    /// void swe_set_topo(
+    ///     double geolon,  /* geographic longitude, eastern positive */
+    ///     double geolat,  /* geographic latitude, northern positive */
+    ///     double geoalt); /* altitude above sea in meters */
+    pub fn swe_set_topo(geolon: c_double, geolat: c_double, geoalt: c_double);
+
+    /*
+     * 11. Sidereal mode functions
+     */
+
+    /// void swe_set_sid_mode(
+    ///     int32 sid_mode,
+    ///     double t0,        /* reference date */
+    ///     double ayan_t0);  /* initial value of the ayanamsha */
+    pub fn swe_set_sid_mode(sid_mode: c_int, t0: c_double, ayan_t0: c_double);
+
+    /// int32 swe_get_ayanamsa_ex_ut(
+    ///     double tjd_ut,    /* Julian day number in UT */
+    ///     int32 ephe_flag,  /* ephemeris flag, one of SEFLG_SWIEPH,
+    ///                          SEFLG_JPLEPH, SEFLG_MOSEPH, plus SEFLG_NONUT */
+    ///     double *daya,     /* output: ayanamsha value (pointer to double) */
+    ///     char *serr);      /* output: error message or warning
+    ///                          (pointer to string) */
+    pub fn swe_get_ayanamsa_ex_ut(
+        tjd_ut: c_double,
+        ephe_flag: c_int,
+        daya: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// int32 swe_get_ayanamsa_ex(
+    ///     double tjd_et,    /* Julian day number in ET/TT */
+    ///     int32 ephe_flag,
+    ///     double *daya,
+    ///     char *serr);
+    pub fn swe_get_ayanamsa_ex(
+        tjd_et: c_double,
+        ephe_flag: c_int,
+        daya: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /// const char *swe_get_ayanamsa_name(
+    ///     int32 isidmode)
+    pub fn swe_get_ayanamsa_name(isidmode: c_int) -> *const c_char;
+
     /*
      * 14. House cups calculation
      */
@@ -172,10 +802,113 @@ extern "C" {
         ascmc: *mut c_double,
     ) -> c_int;
 
+    /// int swe_houses_armc(
+    ///     double armc,
+    ///     double geolat,
+    ///     double eps,
+    ///     int hsys,
+    ///     double *cusps,
+    ///     double *ascmc);
+    pub fn swe_houses_armc(
+        armc: c_double,
+        geolat: c_double,
+        eps: c_double,
+        hsys: c_int,
+        cusps: *mut c_double,
+        ascmc: *mut c_double,
+    ) -> c_int;
+
+    /*
+     * 15. House position of a planet
+     */
+
+    /// double swe_house_pos(
+    ///     double armc,      /* ARMC */
+    ///     double geolat,    /* geographic latitude, in degrees */
+    ///     double eps,       /* ecliptic obliquity, in degrees */
+    ///     int hsys,         /* house method, one of the letters PKRCAV */
+    ///     double *xpin,     /* array of 2 doubles: ecl. longitude and
+    ///                          latitude of the planet */
+    ///     char *serr);      /* return area for error or warning message */
+    pub fn swe_house_pos(
+        armc: c_double,
+        geolat: c_double,
+        eps: c_double,
+        hsys: c_int,
+        xpin: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_double;
+
+    /// int32 swe_gauquelin_sector(
+    ///     double t_ut,      /* input time (UT) */
+    ///     int32 ipl,        /* planet number, if planet, or moon */
+    ///     char *starname,   /* star name, if star */
+    ///     int32 iflag,      /* flag for ephemeris and SEFLG_TOPOCTR */
+    ///     int32 imeth,      /* method: 0 = with lat., 1 = without lat.,
+    ///                          2 = from rise/set, 3 = from rise/set with
+    ///                          refraction */
+    ///     double *geopos,   /* array of three doubles containing geograph.
+    ///                          long., lat., height of observer */
+    ///     double atpress,   /* atmospheric pressure */
+    ///     double attemp,    /* atmospheric temperature in degrees Celsius */
+    ///     double *dgsect,   /* return address for gauquelin sector
+    ///                          position */
+    ///     char *serr);      /* return address for error message */
+    pub fn swe_gauquelin_sector(
+        t_ut: c_double,
+        ipl: c_int,
+        starname: *mut c_char,
+        iflag: c_int,
+        imeth: c_int,
+        geopos: *mut c_double,
+        atpress: c_double,
+        attemp: c_double,
+        dgsect: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+
+    /*
+     * 16. Sidereal time
+     */
+
+    /// double swe_sidtime(
+    ///     double tjd_ut);   /* Julian day number, UT */
+    pub fn swe_sidtime(tjd_ut: c_double) -> c_double;
+
+    /// double swe_sidtime0(
+    ///     double tjd_ut,    /* Julian day number, UT */
+    ///     double eps,       /* obliquity of ecliptic, in degrees */
+    ///     double nut);      /* nutation in longitude, in degrees */
+    pub fn swe_sidtime0(
+        tjd_ut: c_double,
+        eps: c_double,
+        nut: c_double,
+    ) -> c_double;
+
     /*
      * 17. Auxiliary functions
      */
 
+    /// void swe_cotrans(
+    ///     double *xpo,      /* 3 doubles: long., lat., dist. to be
+    ///                          converted; distance remains unchanged */
+    ///     double *xpn,      /* 3 doubles: long., lat., dist. Result of the
+    ///                          conversion */
+    ///     double eps);      /* obliquity of ecliptic, in degrees. */
+    pub fn swe_cotrans(xpo: *mut c_double, xpn: *mut c_double, eps: c_double);
+
+    /// void swe_cotrans_sp(
+    ///     double *xpo,      /* 6 doubles, input: long., lat., dist. and
+    ///                          speeds in long., lat and dist. */
+    ///     double *xpn,      /* 6 doubles, position and speed in new
+    ///                          coordinate system */
+    ///     double eps);      /* obliquity of ecliptic, in degrees. */
+    pub fn swe_cotrans_sp(
+        xpo: *mut c_double,
+        xpn: *mut c_double,
+        eps: c_double,
+    );
+
     /// double swe_degnorm(double x);
     pub fn swe_degnorm(x: c_double) -> c_double;
 
@@ -2,11 +2,14 @@ extern crate serde;
 extern crate serde_derive;
 extern crate serde_json;
 extern crate strum;
+use crate::sweerror::{self, SweError};
 use crate::swerust::handler_swe17::{split_deg, SplitDegResult};
 use num_derive::FromPrimitive;
 //use num_traits::FromPrimitive;
 use serde::{Deserialize, Serialize};
-use strum::AsStaticRef;
+use std::ops::{BitOr, BitOrAssign};
+use std::os::raw::c_char;
+use strum::{AsStaticRef, IntoEnumIterator};
 
 /// Language available (for crate "astrology", "libastro")
 #[derive(Debug, Clone, Copy, PartialEq, FromPrimitive)]
@@ -344,6 +347,33 @@ impl Bodies {
         }
     }
 
+    /// ipl for swe_calc and the functions using it, Err for NPlanets and the
+    /// bodies computed by handler_swe03 only (SouthNode, FortunaPart)
+    pub(crate) fn calc_ipl(self) -> Result<i32, SweError> {
+        match self {
+            Bodies::NPlanets | Bodies::SouthNode | Bodies::FortunaPart => {
+                Err(SweError::InvalidBody(self as i32))
+            },
+            _ => Ok(self as i32),
+        }
+    }
+
+    /// ipl for swe_get_orbital_elements, Err for the bodies without an
+    /// orbit (Sun, nodes and apsides)
+    pub(crate) fn orbit_ipl(self) -> Result<i32, SweError> {
+        match self {
+            Bodies::EclNut
+            | Bodies::Sun
+            | Bodies::MeanNode
+            | Bodies::TrueNode
+            | Bodies::MeanApog
+            | Bodies::OscuApog
+            | Bodies::IntpApog
+            | Bodies::IntpPerg => Err(SweError::InvalidBody(self as i32)),
+            _ => self.calc_ipl(),
+        }
+    }
+
     /// Object color
     pub fn object_color(self, theme: Theme) -> i32 {
         match theme {
@@ -497,8 +527,23 @@ pub enum Calandar {
     Gregorian = 1,
 }
 
+/// Day of the week (swe_day_of_week)
+#[derive(
+    Debug, Clone, Copy, PartialEq, Display, EnumIter, AsStaticStr, FromPrimitive,
+)]
+pub enum Weekday {
+    Monday = 0,
+    Tuesday = 1,
+    Wednesday = 2,
+    Thursday = 3,
+    Friday = 4,
+    Saturday = 5,
+    Sunday = 6,
+}
+
 #[allow(clippy::upper_case_acronyms)]
 /// Optional flag swissephem
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OptionalFlag {
     JplEph = 1,
     SwissEph = 2,
@@ -511,9 +556,8 @@ pub enum OptionalFlag {
     Speed = 256,
     NoGravitanionalDeflection = 512,
     NoAnnualAberration = 1024,
+    /// NoAnnualAberration | NoGravitanionalDeflection
     AstronomicPosition = 1024 | 512,
-    // AstronomicPosition = OptionalFlag::NoAnnualAberration
-    //     | OptionalFlag::NoGravitanionalDeflection,
     EquatorialPosition = 2 * 1024,
     XYZCartesianNotPolarCoordinate = 4 * 1024,
     Radians = 8 * 1024,
@@ -525,19 +569,809 @@ pub enum OptionalFlag {
     JplHorApprox = 512 * 1024,
 }
 
-/// House system
-/// I have put in enum only the most important houses methods
-/// To do
+/// Calculation flags (iflag) for swe_calc_ut, swe_pheno_ut, swe_houses_ex
+///
+/// Combination of OptionalFlag:
+/// CalcFlags::from(OptionalFlag::Speed) | OptionalFlag::EquatorialPosition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CalcFlags(i32);
+
+impl CalcFlags {
+    /// No flag, Swiss Ephemeris without speed
+    pub fn new() -> CalcFlags {
+        CalcFlags(0)
+    }
+
+    /// Add a flag
+    pub fn with(self, flag: OptionalFlag) -> CalcFlags {
+        CalcFlags(self.0 | flag as i32)
+    }
+
+    /// Remove a flag
+    pub fn without(self, flag: OptionalFlag) -> CalcFlags {
+        CalcFlags(self.0 & !(flag as i32))
+    }
+
+    /// All the bits of flag are set
+    pub fn contains(self, flag: OptionalFlag) -> bool {
+        self.0 & flag as i32 == flag as i32
+    }
+
+    /// Only the ephemeris flags (SEFLG_EPHMASK), for the calculations
+    /// ignoring the other flags
+    pub fn ephemeris(self) -> CalcFlags {
+        use OptionalFlag::{JplEph, Moshier, SwissEph};
+        CalcFlags(self.0 & (JplEph as i32 | SwissEph as i32 | Moshier as i32))
+    }
+
+    /// Value for the c library
+    pub fn bits(self) -> i32 {
+        self.0
+    }
+
+    /// Err(SweError::InvalidFlags) for the combinations refused or ignored
+    /// silently by the c library
+    pub fn validate(self) -> Result<CalcFlags, SweError> {
+        use OptionalFlag::*;
+        let ephemeris: Vec<OptionalFlag> = [JplEph, SwissEph, Moshier]
+            .iter()
+            .copied()
+            .filter(|f| self.contains(*f))
+            .collect();
+        if ephemeris.len() > 1 {
+            return Err(SweError::InvalidFlags(format!(
+                "only one ephemeris allowed: {:?}",
+                ephemeris
+            )));
+        }
+        let incompatible = [
+            (Heliocentric, TopocentricPosition),
+            (Heliocentric, BarycentricPosition),
+            (BarycentricPosition, TopocentricPosition),
+            (Moshier, BarycentricPosition),
+        ];
+        for (a, b) in incompatible.iter() {
+            if self.contains(*a) && self.contains(*b) {
+                return Err(SweError::InvalidFlags(format!(
+                    "{:?} and {:?} are incompatible",
+                    a, b
+                )));
+            }
+        }
+        Ok(self)
+    }
+}
+
+impl From<OptionalFlag> for CalcFlags {
+    fn from(flag: OptionalFlag) -> CalcFlags {
+        CalcFlags(flag as i32)
+    }
+}
+
+impl BitOr for CalcFlags {
+    type Output = CalcFlags;
+    fn bitor(self, rhs: CalcFlags) -> CalcFlags {
+        CalcFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOr<OptionalFlag> for CalcFlags {
+    type Output = CalcFlags;
+    fn bitor(self, rhs: OptionalFlag) -> CalcFlags {
+        self.with(rhs)
+    }
+}
+
+impl BitOr for OptionalFlag {
+    type Output = CalcFlags;
+    fn bitor(self, rhs: OptionalFlag) -> CalcFlags {
+        CalcFlags::from(self).with(rhs)
+    }
+}
+
+impl BitOrAssign<OptionalFlag> for CalcFlags {
+    fn bitor_assign(&mut self, rhs: OptionalFlag) {
+        *self = self.with(rhs);
+    }
+}
+
+/// Fixed star search in sefstars.txt (swe_fixstar2)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fixstar {
+    /// Traditional name ("Aldebaran")
+    Name(String),
+    /// Bayer designation ("alTau")
+    Bayer(String),
+    /// Sequential number of the star, in the order of the Bayer
+    /// designations (1 is "109Vir" in the sefstars.txt of the c library)
+    Number(u32),
+}
+
+impl Fixstar {
+    /// Search string for the c library
+    pub fn search(&self) -> String {
+        match self {
+            Fixstar::Name(name) => name.clone(),
+            Fixstar::Bayer(bayer) => format!(",{}", bayer),
+            Fixstar::Number(number) => number.to_string(),
+        }
+    }
+}
+
+/// Planet or fixed star, for the functions with ipl and starname
+#[derive(Debug, Clone, PartialEq)]
+pub enum BodyOrStar {
+    Body(Bodies),
+    Star(Fixstar),
+}
+
+impl BodyOrStar {
+    /// ipl for the c library
+    ///
+    /// SE_ECL_NUT with a star: swe_rise_trans ignores the star name in its
+    /// fast method for the planets (SE_SUN to SE_TRUE_NODE)
+    pub(crate) fn ipl(&self) -> Result<i32, SweError> {
+        match self {
+            BodyOrStar::Body(body) => body.calc_ipl(),
+            BodyOrStar::Star(_) => Ok(Bodies::EclNut as i32),
+        }
+    }
+
+    /// Call of the c library for this body or star, to classify its errors
+    pub(crate) fn call(&self, tjd: f64, iflag: i32) -> sweerror::Call {
+        match self {
+            BodyOrStar::Body(body) => sweerror::Call::Body {
+                ipl: *body as i32,
+                tjd,
+                iflag,
+            },
+            BodyOrStar::Star(_) => sweerror::Call::Star,
+        }
+    }
+
+    /// starname for the c library, None (NULL) for a planet
+    pub(crate) fn star_buf(
+        &self,
+    ) -> Result<Option<[c_char; sweerror::STAR_LEN]>, SweError> {
+        match self {
+            BodyOrStar::Body(_) => Ok(None),
+            BodyOrStar::Star(star) => {
+                sweerror::to_star_buf(&star.search()).map(Some)
+            },
+        }
+    }
+
+    /// ObjectName of the heliacal functions, Err for the bodies without
+    /// heliacal events (only the Sun, the Moon and the planets from Mercury
+    /// to Neptune)
+    pub(crate) fn object_name(
+        &self,
+    ) -> Result<[c_char; sweerror::STAR_LEN], SweError> {
+        let name = match self {
+            BodyOrStar::Body(body) => match body {
+                Bodies::Sun => "sun",
+                Bodies::Moon => "moon",
+                Bodies::Mercury => "mercury",
+                Bodies::Venus => "venus",
+                Bodies::Mars => "mars",
+                Bodies::Jupiter => "jupiter",
+                Bodies::Saturn => "saturn",
+                Bodies::Uranus => "uranus",
+                Bodies::Neptune => "neptune",
+                _ => return Err(SweError::InvalidBody(*body as i32)),
+            }
+            .to_string(),
+            BodyOrStar::Star(star) => star.search(),
+        };
+        sweerror::to_star_buf(&name)
+    }
+}
+
+impl From<Bodies> for BodyOrStar {
+    fn from(body: Bodies) -> BodyOrStar {
+        BodyOrStar::Body(body)
+    }
+}
+
+impl From<Fixstar> for BodyOrStar {
+    fn from(star: Fixstar) -> BodyOrStar {
+        BodyOrStar::Star(star)
+    }
+}
+
+/// Geographic position of an observer (geopos in the c library)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GeoPosition {
+    /// Longitude in degree, east positive
+    pub longitude: f64,
+    /// Latitude in degree, north positive
+    pub latitude: f64,
+    /// Altitude above sea level in meter
+    pub altitude: f64,
+}
+
+impl GeoPosition {
+    /// Constructor
+    pub fn new(longitude: f64, latitude: f64, altitude: f64) -> GeoPosition {
+        GeoPosition {
+            longitude,
+            latitude,
+            altitude,
+        }
+    }
+
+    /// Array [longitude, latitude, altitude] for the c library
+    pub fn geopos(self) -> [f64; 3] {
+        [self.longitude, self.latitude, self.altitude]
+    }
+}
+
+/// Ayanamsa (sidereal mode) for OptionalFlag::SideralPosition
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter, AsStaticStr)]
+pub enum Ayanamsa {
+    FaganBradley = 0,
+    Lahiri = 1,
+    DeLuce = 2,
+    Raman = 3,
+    UshaShashi = 4,
+    Krishnamurti = 5,
+    DjwhalKhul = 6,
+    Yukteshwar = 7,
+    JnBhasin = 8,
+    BabylKugler1 = 9,
+    BabylKugler2 = 10,
+    BabylKugler3 = 11,
+    BabylHuber = 12,
+    BabylEtPsc = 13,
+    Aldebaran15Tau = 14,
+    Hipparchos = 15,
+    Sassanian = 16,
+    GalCent0Sag = 17,
+    J2000 = 18,
+    J1900 = 19,
+    B1950 = 20,
+    Suryasiddhanta = 21,
+    SuryasiddhantaMeanSun = 22,
+    Aryabhata = 23,
+    AryabhataMeanSun = 24,
+    SsRevati = 25,
+    SsCitra = 26,
+    TrueCitra = 27,
+    TrueRevati = 28,
+    TruePushya = 29,
+    GalCentRGilbrand = 30,
+    GalEquIau1958 = 31,
+    GalEquTrue = 32,
+    GalEquMula = 33,
+    GalAlignMardyks = 34,
+    TrueMula = 35,
+    GalCentMulaWilhelm = 36,
+    Aryabhata522 = 37,
+    BabylBritton = 38,
+    TrueSheoran = 39,
+    GalCentCochrane = 40,
+    GalEquFiorenza = 41,
+    ValensMoon = 42,
+    /// User defined with t0 (julian day TT) and ayan_t0
+    User = 255,
+}
+
+/// Eclipse flags of the c library (SE_ECL_*)
+///
+/// Some values are shared between eclipses and occultations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EclipseFlag {
+    Central = 1,
+    NonCentral = 2,
+    Total = 4,
+    Annular = 8,
+    Partial = 16,
+    /// Hybrid eclipse, annular and total
+    AnnularTotal = 32,
+    Penumbral = 64,
+    Visible = 128,
+    MaxVisible = 256,
+    /// First contact (SE_ECL_PARTBEG_VISIBLE for lunar eclipses)
+    FirstVisible = 512,
+    /// Second contact (SE_ECL_TOTBEG_VISIBLE for lunar eclipses)
+    SecondVisible = 1024,
+    /// Third contact (SE_ECL_TOTEND_VISIBLE for lunar eclipses)
+    ThirdVisible = 2048,
+    /// Fourth contact (SE_ECL_PARTEND_VISIBLE for lunar eclipses)
+    FourthVisible = 4096,
+    /// SE_ECL_OCC_BEG_DAYLIGHT for occultations
+    PenumbralBeginVisible = 8192,
+    /// SE_ECL_OCC_END_DAYLIGHT for occultations
+    PenumbralEndVisible = 16384,
+}
+
+/// Combination of EclipseFlag, eclipse type searched (ifltype) or returned
+/// by the eclipse functions (retflag)
+///
+/// EclipseFlags::new() searches any type of eclipse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EclipseFlags(i32);
+
+impl EclipseFlags {
+    /// No flag
+    pub fn new() -> EclipseFlags {
+        EclipseFlags(0)
+    }
+
+    /// Flags returned by the c library
+    pub fn from_bits(bits: i32) -> EclipseFlags {
+        EclipseFlags(bits)
+    }
+
+    /// Add a flag
+    pub fn with(self, flag: EclipseFlag) -> EclipseFlags {
+        EclipseFlags(self.0 | flag as i32)
+    }
+
+    /// All the bits of flag are set
+    pub fn contains(self, flag: EclipseFlag) -> bool {
+        self.0 & flag as i32 == flag as i32
+    }
+
+    /// Value for the c library
+    pub fn bits(self) -> i32 {
+        self.0
+    }
+
+    /// Type of the eclipse, None if no eclipse
+    pub fn eclipse_type(self) -> Option<EclipseType> {
+        use EclipseType::*;
+        [Total, Annular, AnnularTotal, Partial, Penumbral]
+            .iter()
+            .copied()
+            .find(|t| self.0 & *t as i32 != 0)
+    }
+}
+
+impl From<EclipseFlag> for EclipseFlags {
+    fn from(flag: EclipseFlag) -> EclipseFlags {
+        EclipseFlags(flag as i32)
+    }
+}
+
+impl From<EclipseType> for EclipseFlags {
+    fn from(eclipse_type: EclipseType) -> EclipseFlags {
+        EclipseFlags(eclipse_type as i32)
+    }
+}
+
+impl BitOr for EclipseFlags {
+    type Output = EclipseFlags;
+    fn bitor(self, rhs: EclipseFlags) -> EclipseFlags {
+        EclipseFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOr<EclipseFlag> for EclipseFlags {
+    type Output = EclipseFlags;
+    fn bitor(self, rhs: EclipseFlag) -> EclipseFlags {
+        self.with(rhs)
+    }
+}
+
+impl BitOr for EclipseFlag {
+    type Output = EclipseFlags;
+    fn bitor(self, rhs: EclipseFlag) -> EclipseFlags {
+        EclipseFlags::from(self).with(rhs)
+    }
+}
+
+/// Type of a solar or lunar eclipse
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter, AsStaticStr)]
+pub enum EclipseType {
+    Total = 4,
+    Annular = 8,
+    Partial = 16,
+    /// Hybrid, annular and total
+    AnnularTotal = 32,
+    /// Lunar eclipse only
+    Penumbral = 64,
+}
+
+/// Event searched by swe_rise_trans (SE_CALC_*)
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter, AsStaticStr)]
+pub enum RiseTransEvent {
+    Rise = 1,
+    Set = 2,
+    /// Upper meridian transit (southern for northern latitudes)
+    UpperTransit = 4,
+    /// Lower meridian transit (northern, below the horizon)
+    LowerTransit = 8,
+}
+
+/// Options of the rising and setting (SE_BIT_*)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RiseTransFlag {
+    /// Rising or setting of the disc center (upper limb by default)
+    DiscCenter = 256,
+    /// Rising or setting of the lower limb of the disc
+    DiscBottom = 8192,
+    /// Geocentric position, ecliptic latitude ignored
+    GeoctrNoEclLat = 128,
+    NoRefraction = 512,
+    /// Sun at -6 degrees
+    CivilTwilight = 1024,
+    /// Sun at -12 degrees
+    NauticTwilight = 2048,
+    /// Sun at -18 degrees
+    AstroTwilight = 4096,
+    /// Neglect the effect of distance on disc size
+    FixedDiscSize = 16384,
+    /// Hindu astrology: DiscCenter | NoRefraction | GeoctrNoEclLat
+    HinduRising = 256 | 512 | 128,
+}
+
+/// Event and options for swe_rise_trans (rsmi)
+///
+/// RiseTransFlags::new(RiseTransEvent::Rise).with(RiseTransFlag::DiscCenter)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RiseTransFlags(i32);
+
+impl RiseTransFlags {
+    /// Event without option
+    pub fn new(event: RiseTransEvent) -> RiseTransFlags {
+        RiseTransFlags(event as i32)
+    }
+
+    /// Add an option
+    pub fn with(self, flag: RiseTransFlag) -> RiseTransFlags {
+        RiseTransFlags(self.0 | flag as i32)
+    }
+
+    /// All the bits of flag are set
+    pub fn contains(self, flag: RiseTransFlag) -> bool {
+        self.0 & flag as i32 == flag as i32
+    }
+
+    /// Event searched
+    pub fn event(self) -> RiseTransEvent {
+        match self.0 & 15 {
+            2 => RiseTransEvent::Set,
+            4 => RiseTransEvent::UpperTransit,
+            8 => RiseTransEvent::LowerTransit,
+            _ => RiseTransEvent::Rise,
+        }
+    }
+
+    /// Value for the c library
+    pub fn bits(self) -> i32 {
+        self.0
+    }
+}
+
+impl From<RiseTransEvent> for RiseTransFlags {
+    fn from(event: RiseTransEvent) -> RiseTransFlags {
+        RiseTransFlags::new(event)
+    }
+}
+
+impl BitOr<RiseTransFlag> for RiseTransFlags {
+    type Output = RiseTransFlags;
+    fn bitor(self, rhs: RiseTransFlag) -> RiseTransFlags {
+        self.with(rhs)
+    }
+}
+
+impl BitOr<RiseTransFlag> for RiseTransEvent {
+    type Output = RiseTransFlags;
+    fn bitor(self, rhs: RiseTransFlag) -> RiseTransFlags {
+        RiseTransFlags::new(self).with(rhs)
+    }
+}
+
+/// Method of swe_nod_aps (SE_NODBIT_*)
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter, AsStaticStr)]
+pub enum NodApsMethod {
+    /// Mean nodes and apsides for the Moon and the planets Mercury to
+    /// Neptune, osculating for Pluto and the asteroids
+    Mean = 1,
+    /// Osculating nodes and apsides for all the bodies
+    Osculating = 2,
+    /// Osculating, calculated from the barycentric positions and speed for
+    /// the planets beyond Jupiter
+    OsculatingBarycentric = 4,
+    /// Mean for the planets Mercury to Neptune, OsculatingBarycentric for
+    /// the other bodies
+    MeanOsculatingBarycentric = 1 | 4,
+}
+
+/// Ecliptic or equatorial coordinates (calc_flag of swe_azalt and
+/// swe_azalt_rev)
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter, AsStaticStr)]
+pub enum CoordinateSystem {
+    /// Longitude and latitude
+    Ecliptic = 0,
+    /// Right ascension and declination (OptionalFlag::EquatorialPosition)
+    Equatorial = 1,
+}
+
+/// Direction of the conversion of swe_refrac_extended (calc_flag)
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter, AsStaticStr)]
+pub enum Refraction {
+    TrueToApparent = 0,
+    ApparentToTrue = 1,
+}
+
+/// Atmospheric conditions for the heliacal events (datm)
+///
+/// With Atmosphere::default() the c library estimates the values from the
+/// altitude of the observer
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Atmosphere {
+    /// Atmospheric pressure in mbar (hPa)
+    pub pressure: f64,
+    /// Temperature in degrees Celsius
+    pub temperature: f64,
+    /// Relative humidity in %
+    pub humidity: f64,
+    /// Meteorological range in km if >= 1, total atmospheric coefficient
+    /// (ktot) if between 0 and 1, calculated if 0
+    pub extinction: f64,
+}
+
+impl Atmosphere {
+    /// Array for the c library
+    pub fn datm(self) -> [f64; 4] {
+        [
+            self.pressure,
+            self.temperature,
+            self.humidity,
+            self.extinction,
+        ]
+    }
+}
+
+/// Observer of the heliacal events (dobs)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Observer {
+    /// Age in years
+    pub age: f64,
+    /// Snellen ratio of the eyes (1 is normal)
+    pub snellen_ratio: f64,
+    /// The optical parameters below are used only with
+    /// HeliacalFlag::OpticalParams
+    pub binocular: bool,
+    /// Telescope magnification, 1 for the naked eye
+    pub magnification: f64,
+    /// Optical aperture (telescope diameter) in mm
+    pub aperture: f64,
+    /// Optical transmission
+    pub transmission: f64,
+}
+
+impl Default for Observer {
+    /// Naked eye, 36 years, normal eyes
+    fn default() -> Observer {
+        Observer {
+            age: 36.0,
+            snellen_ratio: 1.0,
+            binocular: true,
+            magnification: 1.0,
+            aperture: 0.0,
+            transmission: 0.0,
+        }
+    }
+}
+
+impl Observer {
+    /// Array for the c library
+    pub fn dobs(self) -> [f64; 6] {
+        [
+            self.age,
+            self.snellen_ratio,
+            if self.binocular { 1.0 } else { 0.0 },
+            self.magnification,
+            self.aperture,
+            self.transmission,
+        ]
+    }
+}
+
+/// Heliacal event (event_type of swe_heliacal_ut)
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter, AsStaticStr)]
+pub enum HeliacalEvent {
+    /// Morning first, for all the visible planets and stars
+    HeliacalRising = 1,
+    /// Evening last, for all the visible planets and stars
+    HeliacalSetting = 2,
+    /// Mercury, Venus and the Moon
+    EveningFirst = 3,
+    /// Mercury, Venus and the Moon
+    MorningLast = 4,
+}
+
+/// Options of the heliacal functions (SE_HELFLAG_*)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeliacalFlag {
+    LongSearch = 128,
+    HighPrecision = 256,
+    /// Use the optical parameters of Observer
+    OpticalParams = 512,
+    /// Only the date, without the optimum and the end of visibility
+    NoDetails = 1024,
+    Search1Period = 2048,
+    /// As if the Sun were at nadir
+    VisLimDark = 4096,
+    /// As if the Moon were at nadir
+    VisLimNoMoon = 8192,
+}
+
+/// Combination of HeliacalFlag, added to the ephemeris of CalcFlags in
+/// helflag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HeliacalFlags(i32);
+
+impl HeliacalFlags {
+    /// No flag
+    pub fn new() -> HeliacalFlags {
+        HeliacalFlags(0)
+    }
+
+    /// Add a flag
+    pub fn with(self, flag: HeliacalFlag) -> HeliacalFlags {
+        HeliacalFlags(self.0 | flag as i32)
+    }
+
+    /// All the bits of flag are set
+    pub fn contains(self, flag: HeliacalFlag) -> bool {
+        self.0 & flag as i32 == flag as i32
+    }
+
+    /// Value for the c library
+    pub fn bits(self) -> i32 {
+        self.0
+    }
+}
+
+impl From<HeliacalFlag> for HeliacalFlags {
+    fn from(flag: HeliacalFlag) -> HeliacalFlags {
+        HeliacalFlags(flag as i32)
+    }
+}
+
+impl BitOr<HeliacalFlag> for HeliacalFlags {
+    type Output = HeliacalFlags;
+    fn bitor(self, rhs: HeliacalFlag) -> HeliacalFlags {
+        self.with(rhs)
+    }
+}
+
+impl BitOr for HeliacalFlag {
+    type Output = HeliacalFlags;
+    fn bitor(self, rhs: HeliacalFlag) -> HeliacalFlags {
+        HeliacalFlags::from(self).with(rhs)
+    }
+}
+
+/// House system (hsys in the c library)
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Display,
+    EnumIter,
+    AsStaticStr,
+    Serialize,
+    Deserialize,
+)]
 pub enum HouseSystem {
+    /// 'B'
+    Alcabitius,
+    /// 'Y'
+    Apc,
+    /// 'C'
     Campanus,
+    /// 'F' Carter poli-equatorial
+    Carter,
+    /// 'E' (or 'A'), equal houses from the ascendant
     Equal,
+    /// 'N' whole signs, the first house begins at 0° Aries
+    EqualAries,
+    /// 'D' equal houses from the MC
+    EqualMc,
+    /// 'G' 36 sectors, numbered clockwise
+    Gauquelin,
+    /// 'H' horizon/azimuth
+    Horizon,
+    /// 'K'
     Koch,
+    /// 'U' Krusinski-Pisa-Goelzer
+    Krusinski,
+    /// 'X' axial rotation system
+    Meridian,
+    /// 'M'
+    Morinus,
+    /// 'P'
     Placidus,
+    /// 'O' Porphyry
     Porphyrius,
+    /// 'L' Pullen sinusoidal delta
+    PullenSd,
+    /// 'Q' Pullen sinusoidal ratio
+    PullenSr,
+    /// 'R'
     Regiomontanus,
+    /// 'S'
+    Sripati,
+    /// 'I' solution Treindl
+    Sunshine,
+    /// 'i' solution Makransky
+    SunshineAlt,
+    /// 'T' Polich/Page
+    Topocentric,
+    /// 'V' equal houses after Vehlow
+    Vehlow,
+    /// 'W'
     WholeSign,
 }
 
+impl HouseSystem {
+    /// Letter of the house system in the c library
+    pub fn hsys(self) -> char {
+        match self {
+            HouseSystem::Alcabitius => 'B',
+            HouseSystem::Apc => 'Y',
+            HouseSystem::Campanus => 'C',
+            HouseSystem::Carter => 'F',
+            HouseSystem::Equal => 'E',
+            HouseSystem::EqualAries => 'N',
+            HouseSystem::EqualMc => 'D',
+            HouseSystem::Gauquelin => 'G',
+            HouseSystem::Horizon => 'H',
+            HouseSystem::Koch => 'K',
+            HouseSystem::Krusinski => 'U',
+            HouseSystem::Meridian => 'X',
+            HouseSystem::Morinus => 'M',
+            HouseSystem::Placidus => 'P',
+            HouseSystem::Porphyrius => 'O',
+            HouseSystem::PullenSd => 'L',
+            HouseSystem::PullenSr => 'Q',
+            HouseSystem::Regiomontanus => 'R',
+            HouseSystem::Sripati => 'S',
+            HouseSystem::Sunshine => 'I',
+            HouseSystem::SunshineAlt => 'i',
+            HouseSystem::Topocentric => 'T',
+            HouseSystem::Vehlow => 'V',
+            HouseSystem::WholeSign => 'W',
+        }
+    }
+
+    /// House system of a letter of the c library
+    /// Err(SweError::InvalidHouseSystem) for an unknown letter (the c library
+    /// would silently use Placidus)
+    pub fn from_hsys(hsys: char) -> Result<HouseSystem, SweError> {
+        if hsys == 'A' {
+            return Ok(HouseSystem::Equal);
+        }
+        HouseSystem::iter()
+            .find(|h| h.hsys() == hsys)
+            .ok_or(SweError::InvalidHouseSystem(hsys))
+    }
+}
+
+/// Method of computation of the Gauquelin sectors (imeth)
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter, AsStaticStr)]
+pub enum GauquelinMethod {
+    /// From the ecliptic longitude and latitude
+    WithLatitude = 0,
+    /// From the ecliptic longitude only
+    WithoutLatitude = 1,
+    /// From the rising and setting of the disc center
+    RiseSetDiscCenter = 2,
+    /// From the rising and setting of the disc center, with refraction
+    RiseSetDiscCenterRefraction = 3,
+    /// From the rising and setting of the disc edge
+    RiseSetDiscEdge = 4,
+    /// From the rising and setting of the disc edge, with refraction
+    RiseSetDiscEdgeRefraction = 5,
+}
+
 /// Aspects
 #[derive(
     Debug,
@@ -682,3 +1516,59 @@ impl AspectsFilter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calc_flags_validate_jpl_moshier() {
+        let iflag =
+            CalcFlags::from(OptionalFlag::JplEph) | OptionalFlag::Moshier;
+        assert!(matches!(iflag.validate(), Err(SweError::InvalidFlags(_))));
+    }
+
+    #[test]
+    fn calc_flags_validate_heliocentric_topocentric() {
+        let iflag = CalcFlags::from(OptionalFlag::Heliocentric)
+            | OptionalFlag::TopocentricPosition;
+        assert!(matches!(iflag.validate(), Err(SweError::InvalidFlags(_))));
+    }
+
+    #[test]
+    fn calc_flags_validate_barycentric_moshier() {
+        let iflag = CalcFlags::from(OptionalFlag::BarycentricPosition)
+            | OptionalFlag::Moshier;
+        assert!(matches!(iflag.validate(), Err(SweError::InvalidFlags(_))));
+    }
+
+    #[test]
+    fn calc_flags_validate_valid() {
+        let iflag = CalcFlags::from(OptionalFlag::Speed)
+            | OptionalFlag::SwissEph
+            | OptionalFlag::TopocentricPosition
+            | OptionalFlag::EquatorialPosition;
+        assert_eq!(iflag.validate(), Ok(iflag));
+        assert_eq!(CalcFlags::new().validate(), Ok(CalcFlags::new()));
+    }
+
+    #[test]
+    fn house_system_hsys_round_trip() {
+        assert_eq!(HouseSystem::iter().count(), 24);
+        for hsys in HouseSystem::iter() {
+            assert_eq!(HouseSystem::from_hsys(hsys.hsys()), Ok(hsys));
+        }
+        // Other letter of the equal houses in the c library
+        assert_eq!(HouseSystem::from_hsys('A'), Ok(HouseSystem::Equal));
+    }
+
+    #[test]
+    fn house_system_unknown_letter() {
+        for hsys in ['Z', 'a', 'p', '1', ' '].iter() {
+            assert_eq!(
+                HouseSystem::from_hsys(*hsys),
+                Err(SweError::InvalidHouseSystem(*hsys))
+            );
+        }
+    }
+}
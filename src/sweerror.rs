@@ -0,0 +1,370 @@
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::os::raw::c_char;
+
+/// Size of the "char *serr" buffers given to the c library (AS_MAXCH)
+pub(crate) const SERR_LEN: usize = 256;
+
+/// Error returned by the swerust handlers
+#[derive(Debug, Clone, PartialEq)]
+pub enum SweError {
+    /// The body can't be computed by this function (no orbit for the Sun
+    /// and the nodes, FortunaPart only in calc_ut_fp...)
+    InvalidBody(i32),
+    /// A Swiss Ephemeris or JPL file (*.se1, *.eph) is not on the path
+    EphemerisFileMissing(String),
+    /// The fixed star is not in the star file, or the star file
+    /// (sefstars.txt) is not on the path
+    StarNotFound(String),
+    /// The julian day is outside of the range of the ephemeris
+    DateOutOfRange(String),
+    /// The date or the time given is not valid (31 February, 25:00...)
+    InvalidDate(String),
+    /// The ephemeris asked has not been used, the c library has fallen back
+    /// to the Moshier ephemeris (less precise, but the result is valid)
+    MoshierFallback(String),
+    /// The house system can't be computed at this latitude (polar circle),
+    /// the c library has switched to Porphyry
+    HouseSystem { hsys: char, geolat: f64 },
+    /// The letter is not a house system of the c library
+    InvalidHouseSystem(char),
+    /// Combination of calculation flags not possible
+    InvalidFlags(String),
+    /// A path or a name given is longer than the c buffer (255 char)
+    PathTooLong(usize),
+    /// A string given contains a nul byte
+    NulByte(String),
+    /// A string returned by the c library is not valid utf-8
+    InvalidUtf8(String),
+    /// Other error reported by the c library in "serr"
+    Calculation(String),
+}
+
+/// What a call of the c library computes, the c library returns ERR (-1)
+/// for all its errors: the error is classified from the inputs of the call,
+/// "serr" is only the message
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Call {
+    /// A body (ipl) at a julian day, with the ephemeris flags of iflag
+    Body { ipl: i32, tjd: f64, iflag: i32 },
+    /// A fixed star, searched in sefstars.txt
+    Star,
+    /// A calendar date (swe_utc_to_jd)
+    Date,
+    /// Other functions (house position...)
+    Other,
+}
+
+// SEFLG_MOSEPH
+const MOSEPH: i32 = 4;
+// MOSHPLEPH_START, MOSHPLEPH_END (sweph.h)
+const MOSHIER_RANGE: (f64, f64) = (625_000.5, 2_818_000.5);
+// JPL_DE431_START, JPL_DE431_END, range of the Swiss Ephemeris files
+const FILES_RANGE: (f64, f64) = (-3_027_215.5, 7_930_192.5);
+// CHIRON_START, CHIRON_END
+const CHIRON_RANGE: (f64, f64) = (1_967_601.5, 3_419_437.5);
+// PHOLUS_START, PHOLUS_END
+const PHOLUS_RANGE: (f64, f64) = (640_648.5, 4_390_617.5);
+
+impl Call {
+    /// Error of the call when the c library has returned ERR
+    ///
+    /// - Body: DateOutOfRange if tjd is outside of the ephemeris,
+    ///   EphemerisFileMissing if the body needs a file at this date (the
+    ///   asteroids, or the planets outside of the range of Moshier)
+    /// - Star: StarNotFound, also if sefstars.txt is not on the path
+    /// - Date: InvalidDate
+    pub(crate) fn error(self, serr: String) -> SweError {
+        match self {
+            Call::Body { ipl, tjd, iflag } => {
+                // Chiron to Vesta, and the asteroids of SE_AST_OFFSET
+                let asteroid = (15..=20).contains(&ipl) || ipl > 10000;
+                let moshier = iflag & MOSEPH != 0 && !asteroid;
+                let (start, end) = match ipl {
+                    15 => CHIRON_RANGE,
+                    16 => PHOLUS_RANGE,
+                    _ if moshier => MOSHIER_RANGE,
+                    _ => FILES_RANGE,
+                };
+                if tjd < start || tjd > end {
+                    SweError::DateOutOfRange(serr)
+                } else if asteroid
+                    || (!moshier
+                        && (tjd < MOSHIER_RANGE.0 || tjd > MOSHIER_RANGE.1))
+                {
+                    SweError::EphemerisFileMissing(serr)
+                } else {
+                    SweError::Calculation(serr)
+                }
+            },
+            Call::Star => SweError::StarNotFound(serr),
+            Call::Date => SweError::InvalidDate(serr),
+            Call::Other => SweError::Calculation(serr),
+        }
+    }
+}
+
+impl fmt::Display for SweError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SweError::InvalidBody(ipl) => {
+                write!(f, "illegal planet number {}", ipl)
+            },
+            SweError::EphemerisFileMissing(s) => {
+                write!(f, "ephemeris file missing: {}", s)
+            },
+            SweError::StarNotFound(s) => write!(f, "star not found: {}", s),
+            SweError::DateOutOfRange(s) => {
+                write!(f, "date out of range: {}", s)
+            },
+            SweError::InvalidDate(s) => write!(f, "{}", s),
+            SweError::MoshierFallback(s) => {
+                write!(f, "fallback to Moshier ephemeris: {}", s)
+            },
+            SweError::HouseSystem { hsys, geolat } => write!(
+                f,
+                "house system '{}' not possible at latitude {}, \
+                 switched to Porphyry",
+                hsys, geolat
+            ),
+            SweError::InvalidHouseSystem(hsys) => {
+                write!(f, "invalid house system '{}'", hsys)
+            },
+            SweError::InvalidFlags(s) => {
+                write!(f, "invalid calculation flags: {}", s)
+            },
+            SweError::PathTooLong(len) => {
+                write!(f, "path to long ({} > 255 char)", len)
+            },
+            SweError::NulByte(s) => {
+                write!(f, "string contains a nul byte: {:?}", s)
+            },
+            SweError::InvalidUtf8(s) => {
+                write!(f, "string is not valid utf-8: {}", s)
+            },
+            SweError::Calculation(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl Error for SweError {}
+
+/// Read the "char *serr" buffer filled by the c library
+pub(crate) fn serr_to_string(serr: &[c_char]) -> String {
+    unsafe { CStr::from_ptr(serr.as_ptr()) }
+        .to_string_lossy()
+        .trim()
+        .to_string()
+}
+
+/// Read a string buffer filled by the c library (version, path, name...)
+pub(crate) fn c_buf_to_string(buf: &[c_char]) -> Result<String, SweError> {
+    c_str_to_string(unsafe { CStr::from_ptr(buf.as_ptr()) })
+}
+
+/// Read a string returned by the c library
+pub(crate) fn c_str_to_string(c_str: &CStr) -> Result<String, SweError> {
+    c_str
+        .to_str()
+        .map(|s| s.to_string())
+        .map_err(|_| SweError::InvalidUtf8(c_str.to_string_lossy().into()))
+}
+
+/// Convert a path or a name for the c library (max 255 char)
+pub(crate) fn to_c_string(s: &str) -> Result<CString, SweError> {
+    if s.len() > 255 {
+        return Err(SweError::PathTooLong(s.len()));
+    }
+    CString::new(s).map_err(|_| SweError::NulByte(s.to_string()))
+}
+
+/// Size of the "char *star" buffers (twice SE_MAX_STNAME)
+pub(crate) const STAR_LEN: usize = 2 * 256 + 1;
+
+/// Copy a fixed star search string in a "char *star" buffer, the c library
+/// write the name found in this buffer
+pub(crate) fn to_star_buf(s: &str) -> Result<[c_char; STAR_LEN], SweError> {
+    let c_str = to_c_string(s)?;
+    let mut buf = [0; STAR_LEN];
+    for (b, c) in buf.iter_mut().zip(c_str.as_bytes()) {
+        *b = *c as c_char;
+    }
+    Ok(buf)
+}
+
+/// Err if status is ERR (-1), classified from the call
+pub(crate) fn check_status(
+    status: i32,
+    serr: &[c_char],
+    call: Call,
+) -> Result<(), SweError> {
+    if status < 0 {
+        Err(call.error(serr_to_string(serr)))
+    } else {
+        Ok(())
+    }
+}
+
+/// Warning if the c library has not used the ephemeris asked in iflag
+pub(crate) fn moshier_fallback(
+    iflag: i32,
+    iflag_returned: i32,
+    serr: &[c_char],
+) -> Option<SweError> {
+    if iflag & MOSEPH == 0 && iflag_returned & MOSEPH != 0 {
+        let s = serr_to_string(serr);
+        Some(SweError::MoshierFallback(if s.is_empty() {
+            "using Moshier eph.".to_string()
+        } else {
+            s
+        }))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweconst::{
+        Bodies, BodyOrStar, Calandar, CalcFlags, Fixstar, GeoPosition,
+        OptionalFlag, RiseTransEvent,
+    };
+    use crate::swerust::{
+        handler_swe03, handler_swe05, handler_swe06, handler_swe07,
+        handler_swe08,
+    };
+
+    const J2000: f64 = 2_451_545.0;
+    // Year 3501, after the range of Moshier
+    const TJD_3501: f64 = 3_000_000.0;
+
+    fn body(ipl: Bodies, tjd: f64, iflag: CalcFlags) -> Call {
+        Call::Body {
+            ipl: ipl as i32,
+            tjd,
+            iflag: iflag.bits(),
+        }
+    }
+
+    #[test]
+    fn call_error_body() {
+        let moshier = CalcFlags::from(OptionalFlag::Moshier);
+        let error = |call: Call| call.error("serr".to_string());
+        let serr = || "serr".to_string();
+        assert_eq!(
+            error(body(Bodies::Mars, TJD_3501, CalcFlags::new())),
+            SweError::EphemerisFileMissing(serr())
+        );
+        assert_eq!(
+            error(body(Bodies::Mars, TJD_3501, moshier)),
+            SweError::DateOutOfRange(serr())
+        );
+        assert_eq!(
+            error(body(Bodies::Mars, 1e8, CalcFlags::new())),
+            SweError::DateOutOfRange(serr())
+        );
+        assert_eq!(
+            error(body(Bodies::Ceres, J2000, moshier)),
+            SweError::EphemerisFileMissing(serr())
+        );
+        assert_eq!(
+            error(body(Bodies::Chiron, 1_000_000.0, CalcFlags::new())),
+            SweError::DateOutOfRange(serr())
+        );
+        assert_eq!(
+            error(body(Bodies::Mars, J2000, CalcFlags::new())),
+            SweError::Calculation(serr())
+        );
+    }
+
+    #[test]
+    fn call_error_other() {
+        let serr = || "serr".to_string();
+        assert_eq!(Call::Star.error(serr()), SweError::StarNotFound(serr()));
+        assert_eq!(Call::Date.error(serr()), SweError::InvalidDate(serr()));
+        assert_eq!(Call::Other.error(serr()), SweError::Calculation(serr()));
+    }
+
+    #[test]
+    fn handlers_ephemeris_errors() {
+        // No ephemeris file in the repository
+        let calc = |tjd, ipl, iflag| {
+            handler_swe03::calc_ut(tjd, ipl, iflag).map(|_| ())
+        };
+        assert!(matches!(
+            calc(TJD_3501, Bodies::Mars, CalcFlags::new()),
+            Err(SweError::EphemerisFileMissing(_))
+        ));
+        assert!(matches!(
+            calc(TJD_3501, Bodies::Mars, OptionalFlag::Moshier.into()),
+            Err(SweError::DateOutOfRange(_))
+        ));
+        assert!(matches!(
+            calc(J2000, Bodies::Chiron, CalcFlags::new()),
+            Err(SweError::EphemerisFileMissing(_))
+        ));
+        assert!(matches!(
+            calc(1_000_000.0, Bodies::Chiron, CalcFlags::new()),
+            Err(SweError::DateOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn handlers_invalid_body() {
+        assert!(matches!(
+            handler_swe03::calc_ut(
+                J2000,
+                Bodies::FortunaPart,
+                CalcFlags::new()
+            ),
+            Err(SweError::InvalidBody(25))
+        ));
+        assert!(matches!(
+            handler_swe06::get_orbital_elements(
+                J2000,
+                Bodies::Sun,
+                CalcFlags::new()
+            ),
+            Err(SweError::InvalidBody(0))
+        ));
+    }
+
+    #[test]
+    fn handlers_star_not_found() {
+        let nostar = Fixstar::Name("Nostar".to_string());
+        assert!(matches!(
+            handler_swe05::fixstar2_ut(&nostar, J2000, CalcFlags::new()),
+            Err(SweError::StarNotFound(_))
+        ));
+        assert!(matches!(
+            handler_swe07::rise_trans(
+                J2000,
+                &BodyOrStar::Star(nostar),
+                CalcFlags::new(),
+                RiseTransEvent::Rise.into(),
+                GeoPosition::new(6.15, 46.2, 0.0),
+                0.0,
+                0.0,
+            ),
+            Err(SweError::StarNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn handlers_invalid_date() {
+        assert!(matches!(
+            handler_swe08::utc_to_jd(
+                2000,
+                2,
+                31,
+                0,
+                0,
+                0.0,
+                Calandar::Gregorian
+            ),
+            Err(SweError::InvalidDate(_))
+        ));
+    }
+}
@@ -0,0 +1,1091 @@
+use crate::raw;
+use crate::sweconst::{
+    Atmosphere, Ayanamsa, Bodies, BodyOrStar, Calandar, CalcFlags,
+    CoordinateSystem, EclipseFlags, Fixstar, GauquelinMethod, GeoPosition,
+    HeliacalEvent, HeliacalFlags, HouseSystem, NodApsMethod, Object, Observer,
+    RiseTransFlags,
+};
+use crate::sweerror::{self, SweError};
+use crate::swerust::{
+    coords, handler_swe02, handler_swe03, handler_swe05, handler_swe06,
+    handler_swe07, handler_swe08, handler_swe10, handler_swe11, handler_swe14,
+    handler_swe15, handler_swe16, horizon,
+};
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/*
+ * Session around the state of the c library
+ *
+ * The c library keep the ephemeris path, the jpl file, the open files, the
+ * sidereal mode, the topocentric position... in a state. sweodef.h declares
+ * this state thread local (TLS), except on Apple, WIN32 and DOS32 where it
+ * is global:
+ * - thread local state: a session applies its configuration in the c
+ *   library of the calling thread, sessions in different threads don't see
+ *   each other
+ * - global state (macOS, iOS, Windows): every call of a session is done
+ *   under STATE_LOCK, the lock can be taken again in the same thread (a
+ *   method of the session called in Ephemeris::with)
+ *
+ * The id of the session whose configuration is in the c library is kept
+ * (in each thread or under the lock), the configuration is applied again
+ * only when another session has been used in between, or when the session
+ * has been changed (new id).
+ */
+
+/// State of the c library in each thread
+#[cfg(not(any(target_vendor = "apple", windows)))]
+mod state {
+    use std::cell::Cell;
+
+    thread_local! {
+        /// Id of the session applied in the c library of this thread (None
+        /// after swe_close)
+        static APPLIED: Cell<Option<usize>> = const { Cell::new(None) };
+    }
+
+    /// Nothing to lock, the state of each thread is its own
+    pub(super) struct StateGuard;
+
+    pub(super) fn lock() -> StateGuard {
+        StateGuard
+    }
+
+    pub(super) fn applied() -> Option<usize> {
+        APPLIED.with(Cell::get)
+    }
+
+    pub(super) fn set_applied(id: Option<usize>) {
+        APPLIED.with(|applied| applied.set(id));
+    }
+}
+
+/// State of the c library shared by all the threads
+#[cfg(any(target_vendor = "apple", windows))]
+mod state {
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Mutex, MutexGuard, PoisonError};
+
+    static STATE_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Id of the session applied in the c library (0 after swe_close), only
+    /// changed under STATE_LOCK
+    static APPLIED: AtomicUsize = AtomicUsize::new(0);
+
+    thread_local! {
+        /// This thread holds STATE_LOCK
+        static LOCKED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// STATE_LOCK, None if it was already held by this thread
+    pub(super) struct StateGuard(Option<MutexGuard<'static, ()>>);
+
+    pub(super) fn lock() -> StateGuard {
+        if LOCKED.with(Cell::get) {
+            return StateGuard(None);
+        }
+        // A panic in another session don't corrupt the c state
+        let guard = STATE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        LOCKED.with(|locked| locked.set(true));
+        StateGuard(Some(guard))
+    }
+
+    impl Drop for StateGuard {
+        fn drop(&mut self) {
+            if self.0.is_some() {
+                LOCKED.with(|locked| locked.set(false));
+            }
+        }
+    }
+
+    pub(super) fn applied() -> Option<usize> {
+        match APPLIED.load(Ordering::Relaxed) {
+            0 => None,
+            id => Some(id),
+        }
+    }
+
+    pub(super) fn set_applied(id: Option<usize>) {
+        APPLIED.store(id.unwrap_or(0), Ordering::Relaxed);
+    }
+}
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// SE_FNAME_DFT, swe_close don't reset the name of the JPL file
+const JPL_FILE_DEFAULT: &[u8] = b"de431.eph\0";
+
+/// Ephemeris session
+///
+/// Own the configuration of the c library, all the calculations are done
+/// with this configuration.
+///
+/// A session stays in the thread where it is created (not Send): the state
+/// of the c library is thread local, swe_close on drop can only close the
+/// files opened in this thread. To compute in several threads, create a
+/// session in each thread.
+///
+/// ```compile_fail
+/// fn send<T: Send>(_: T) {}
+/// send(libswe_sys::swerust::Ephemeris::default());
+/// ```
+///
+/// The functions of handler_swe* changing the state of the c library should
+/// be called in Ephemeris::with.
+#[derive(Debug)]
+pub struct Ephemeris {
+    id: usize,
+    /// Not Send and not Sync
+    thread: PhantomData<*const ()>,
+    ephe_path: Option<String>,
+    /// Directory of a custom sefstars.txt, searched before ephe_path
+    star_path: Option<String>,
+    /// star_path;ephe_path for swe_set_ephe_path
+    path: Option<CString>,
+    jpl_file: Option<CString>,
+    /// Ayanamsa, t0, ayan_t0 (handler_swe11::set_sid_mode)
+    sid_mode: Option<(Ayanamsa, f64, f64)>,
+    /// Observer for OptionalFlag::TopocentricPosition
+    topo: Option<GeoPosition>,
+    /// User defined delta T (handler_swe08::set_delta_t_userdef)
+    delta_t_userdef: Option<f64>,
+    /// Tidal acceleration (handler_swe08::set_tid_acc)
+    tid_acc: Option<f64>,
+    /// Lapse rate for the refraction (horizon::set_lapse_rate)
+    lapse_rate: Option<f64>,
+}
+
+impl Default for Ephemeris {
+    /// Session with the default path of the c library (SE_EPHE_PATH)
+    fn default() -> Ephemeris {
+        Ephemeris {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            thread: PhantomData,
+            ephe_path: None,
+            star_path: None,
+            path: None,
+            jpl_file: None,
+            sid_mode: None,
+            topo: None,
+            delta_t_userdef: None,
+            tid_acc: None,
+            lapse_rate: None,
+        }
+    }
+}
+
+impl Ephemeris {
+    /// Constructor with the path of ephemeris
+    pub fn new(ephe_path: &str) -> Result<Ephemeris, SweError> {
+        let mut ephemeris = Ephemeris::default();
+        ephemeris.set_ephe_path(ephe_path)?;
+        Ok(ephemeris)
+    }
+
+    /// Set the path of ephemeris
+    pub fn set_ephe_path(&mut self, path: &str) -> Result<(), SweError> {
+        self.set_path(Some(path.to_string()), self.star_path.clone())
+    }
+
+    /// Set the directory of a custom fixed stars file (sefstars.txt), this
+    /// directory is searched before the path of ephemeris
+    pub fn set_star_path(&mut self, path: &str) -> Result<(), SweError> {
+        self.set_path(self.ephe_path.clone(), Some(path.to_string()))
+    }
+
+    /// Set the JPL file (SEFLG_JPLEPH)
+    pub fn set_jpl_file(&mut self, fname: &str) -> Result<(), SweError> {
+        self.jpl_file = Some(sweerror::to_c_string(fname)?);
+        self.reset();
+        Ok(())
+    }
+
+    /// Set the ayanamsa for OptionalFlag::SideralPosition
+    ///
+    /// t0 (julian day TT) and ayan_t0 are only used with Ayanamsa::User
+    pub fn set_sid_mode(&mut self, ayanamsa: Ayanamsa, t0: f64, ayan_t0: f64) {
+        self.sid_mode = Some((ayanamsa, t0, ayan_t0));
+        self.reset();
+    }
+
+    /// Set the observer for OptionalFlag::TopocentricPosition
+    pub fn set_topo(&mut self, geo: GeoPosition) {
+        self.topo = Some(geo);
+        self.reset();
+    }
+
+    /// Set a user defined delta T in days, None for the automatic delta T
+    pub fn set_delta_t_userdef(&mut self, dt: Option<f64>) {
+        self.delta_t_userdef = dt;
+        self.reset();
+    }
+
+    /// Set the tidal acceleration of the Moon in arcsec/cty^2, None for the
+    /// tidal acceleration of the ephemeris used
+    pub fn set_tid_acc(&mut self, t_acc: Option<f64>) {
+        self.tid_acc = t_acc;
+        self.reset();
+    }
+
+    /// Set the lapse rate of the atmosphere in deg K/m for the apparent
+    /// altitude, None for the standard atmosphere
+    pub fn set_lapse_rate(&mut self, lapse_rate: Option<f64>) {
+        self.lapse_rate = lapse_rate;
+        self.reset();
+    }
+
+    /// Run f with the configuration of this session
+    ///
+    /// For the functions of handler_swe* without method in Ephemeris. f may
+    /// change the state of the c library, the configuration is applied again
+    /// on the next call. The methods of this session can be called in f, a
+    /// method of another session applies the other configuration for the rest
+    /// of f.
+    pub fn with<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        let _guard = state::lock();
+        let result = self.run(f);
+        state::set_applied(None);
+        result
+    }
+
+    /// Run f with the configuration of this session, f don't change the
+    /// state of the c library
+    fn run<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        let _guard = state::lock();
+        self.apply_if_needed();
+        f()
+    }
+
+    /// Same as run, for the functions calling swe_set_topo with their own
+    /// observer (eclipses, risings, heliacal events)
+    fn with_observer<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        let _guard = state::lock();
+        let result = self.run(f);
+        match self.topo {
+            Some(geo) => handler_swe10::set_topo(geo),
+            // The c library can't forget an observer, apply again
+            None => state::set_applied(None),
+        }
+        result
+    }
+
+    /// Get version of swiss ephemeris
+    pub fn version(&self) -> Result<String, SweError> {
+        self.run(handler_swe02::version)
+    }
+
+    /// Get librarx path dll
+    pub fn get_library_path(&self) -> Result<String, SweError> {
+        self.run(handler_swe02::get_library_path)
+    }
+
+    /// See handler_swe03::calc_ut
+    pub fn calc_ut(
+        &self,
+        tjd_ut: f64,
+        ipl: Bodies,
+        iflag: CalcFlags,
+    ) -> Result<handler_swe03::CalcUtResult, SweError> {
+        self.run(|| handler_swe03::calc_ut(tjd_ut, ipl, iflag))
+    }
+
+    /// Topocentric position for the observer geo (see handler_swe03::calc_ut),
+    /// TopocentricPosition is added to iflag
+    ///
+    /// The observer of the session (set_topo) is restored after the
+    /// calculation
+    pub fn calc_ut_topo(
+        &self,
+        tjd_ut: f64,
+        ipl: Bodies,
+        iflag: CalcFlags,
+        geo: GeoPosition,
+    ) -> Result<handler_swe03::CalcUtResult, SweError> {
+        self.with_observer(|| {
+            handler_swe03::calc_ut_topo(tjd_ut, ipl, iflag, geo)
+        })
+    }
+
+    /// See handler_swe03::calc_ut_fp
+    pub fn calc_ut_fp(
+        &self,
+        tjd_ut: f64,
+        geolat: f64,
+        geolong: f64,
+        hsys: HouseSystem,
+        iflag: CalcFlags,
+    ) -> Result<handler_swe03::CalcUtResult, SweError> {
+        self.run(|| {
+            handler_swe03::calc_ut_fp(tjd_ut, geolat, geolong, hsys, iflag)
+        })
+    }
+
+    /// See handler_swe03::calc
+    pub fn calc(
+        &self,
+        tjd_et: f64,
+        ipl: Bodies,
+        iflag: CalcFlags,
+    ) -> Result<handler_swe03::CalcUtResult, SweError> {
+        self.run(|| handler_swe03::calc(tjd_et, ipl, iflag))
+    }
+
+    /// See handler_swe05::fixstar2_ut
+    pub fn fixstar2_ut(
+        &self,
+        star: &Fixstar,
+        tjd_ut: f64,
+        iflag: CalcFlags,
+    ) -> Result<handler_swe05::FixstarResult, SweError> {
+        self.run(|| handler_swe05::fixstar2_ut(star, tjd_ut, iflag))
+    }
+
+    /// See handler_swe05::fixstar2_mag
+    pub fn fixstar2_mag(&self, star: &Fixstar) -> Result<f64, SweError> {
+        self.run(|| handler_swe05::fixstar2_mag(star))
+    }
+
+    /// See coords::true_obliquity
+    pub fn true_obliquity(
+        &self,
+        tjd_ut: f64,
+        iflag: CalcFlags,
+    ) -> Result<f64, SweError> {
+        self.run(|| coords::true_obliquity(tjd_ut, iflag))
+    }
+
+    /// See coords::ecliptic_to_equatorial
+    pub fn ecliptic_to_equatorial(
+        &self,
+        tjd_ut: f64,
+        iflag: CalcFlags,
+        pos: &coords::Coordinates,
+    ) -> Result<coords::Coordinates, SweError> {
+        self.run(|| coords::ecliptic_to_equatorial(tjd_ut, iflag, pos))
+    }
+
+    /// See coords::equatorial_to_ecliptic
+    pub fn equatorial_to_ecliptic(
+        &self,
+        tjd_ut: f64,
+        iflag: CalcFlags,
+        pos: &coords::Coordinates,
+    ) -> Result<coords::Coordinates, SweError> {
+        self.run(|| coords::equatorial_to_ecliptic(tjd_ut, iflag, pos))
+    }
+
+    /// See handler_swe06::nod_aps_ut
+    pub fn nod_aps_ut(
+        &self,
+        tjd_ut: f64,
+        ipl: Bodies,
+        iflag: CalcFlags,
+        method: NodApsMethod,
+        focal_point: bool,
+    ) -> Result<handler_swe06::NodApsResult, SweError> {
+        self.run(|| {
+            handler_swe06::nod_aps_ut(tjd_ut, ipl, iflag, method, focal_point)
+        })
+    }
+
+    /// See handler_swe06::get_orbital_elements
+    pub fn get_orbital_elements(
+        &self,
+        tjd_et: f64,
+        ipl: Bodies,
+        iflag: CalcFlags,
+    ) -> Result<handler_swe06::OrbitalElementsResult, SweError> {
+        self.run(|| handler_swe06::get_orbital_elements(tjd_et, ipl, iflag))
+    }
+
+    /// See handler_swe06::orbit_max_min_true_distance
+    pub fn orbit_max_min_true_distance(
+        &self,
+        tjd_et: f64,
+        ipl: Bodies,
+        iflag: CalcFlags,
+    ) -> Result<handler_swe06::OrbitDistanceResult, SweError> {
+        self.run(|| {
+            handler_swe06::orbit_max_min_true_distance(tjd_et, ipl, iflag)
+        })
+    }
+
+    /// See handler_swe07::pheno_ut
+    pub fn pheno_ut(
+        &self,
+        tjd_ut: f64,
+        ipl: Bodies,
+        iflag: CalcFlags,
+    ) -> Result<handler_swe07::PhenoUtResult, SweError> {
+        self.run(|| handler_swe07::pheno_ut(tjd_ut, ipl, iflag))
+    }
+
+    /// See handler_swe07::sol_eclipse_when_glob
+    pub fn sol_eclipse_when_glob(
+        &self,
+        tjd_start: f64,
+        iflag: CalcFlags,
+        ifltype: EclipseFlags,
+        backward: bool,
+    ) -> Result<handler_swe07::SolEclipseGlobResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::sol_eclipse_when_glob(
+                tjd_start, iflag, ifltype, backward,
+            )
+        })
+    }
+
+    /// See handler_swe07::sol_eclipse_when_loc
+    pub fn sol_eclipse_when_loc(
+        &self,
+        tjd_start: f64,
+        iflag: CalcFlags,
+        geo: GeoPosition,
+        backward: bool,
+    ) -> Result<handler_swe07::SolEclipseLocResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::sol_eclipse_when_loc(tjd_start, iflag, geo, backward)
+        })
+    }
+
+    /// See handler_swe07::sol_eclipse_how
+    pub fn sol_eclipse_how(
+        &self,
+        tjd_ut: f64,
+        iflag: CalcFlags,
+        geo: GeoPosition,
+    ) -> Result<handler_swe07::SolEclipseHowResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::sol_eclipse_how(tjd_ut, iflag, geo)
+        })
+    }
+
+    /// See handler_swe07::sol_eclipse_where
+    pub fn sol_eclipse_where(
+        &self,
+        tjd_ut: f64,
+        iflag: CalcFlags,
+    ) -> Result<handler_swe07::SolEclipseWhereResult, SweError> {
+        self.with_observer(|| handler_swe07::sol_eclipse_where(tjd_ut, iflag))
+    }
+
+    /// See handler_swe07::lun_eclipse_when
+    pub fn lun_eclipse_when(
+        &self,
+        tjd_start: f64,
+        iflag: CalcFlags,
+        ifltype: EclipseFlags,
+        backward: bool,
+    ) -> Result<handler_swe07::LunEclipseResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::lun_eclipse_when(tjd_start, iflag, ifltype, backward)
+        })
+    }
+
+    /// See handler_swe07::lun_eclipse_when_loc
+    pub fn lun_eclipse_when_loc(
+        &self,
+        tjd_start: f64,
+        iflag: CalcFlags,
+        geo: GeoPosition,
+        backward: bool,
+    ) -> Result<handler_swe07::LunEclipseLocResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::lun_eclipse_when_loc(tjd_start, iflag, geo, backward)
+        })
+    }
+
+    /// See handler_swe07::lun_eclipse_how
+    pub fn lun_eclipse_how(
+        &self,
+        tjd_ut: f64,
+        iflag: CalcFlags,
+        geo: Option<GeoPosition>,
+    ) -> Result<handler_swe07::LunEclipseHowResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::lun_eclipse_how(tjd_ut, iflag, geo)
+        })
+    }
+
+    /// See handler_swe07::lun_eclipses
+    pub fn lun_eclipses(
+        &self,
+        tjd_start: f64,
+        tjd_end: f64,
+        iflag: CalcFlags,
+        ifltype: EclipseFlags,
+    ) -> Result<Vec<handler_swe07::LunEclipseResult>, SweError> {
+        self.with_observer(|| {
+            handler_swe07::lun_eclipses(tjd_start, tjd_end, iflag, ifltype)
+        })
+    }
+
+    /// See handler_swe07::lun_occult_when_glob
+    pub fn lun_occult_when_glob(
+        &self,
+        tjd_start: f64,
+        occulted: &BodyOrStar,
+        iflag: CalcFlags,
+        ifltype: EclipseFlags,
+        backward: bool,
+        one_try: bool,
+    ) -> Result<handler_swe07::OccultGlobResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::lun_occult_when_glob(
+                tjd_start, occulted, iflag, ifltype, backward, one_try,
+            )
+        })
+    }
+
+    /// See handler_swe07::lun_occult_when_loc
+    pub fn lun_occult_when_loc(
+        &self,
+        tjd_start: f64,
+        occulted: &BodyOrStar,
+        iflag: CalcFlags,
+        geo: GeoPosition,
+        backward: bool,
+        one_try: bool,
+    ) -> Result<handler_swe07::OccultLocResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::lun_occult_when_loc(
+                tjd_start, occulted, iflag, geo, backward, one_try,
+            )
+        })
+    }
+
+    /// See handler_swe07::lun_occult_where
+    pub fn lun_occult_where(
+        &self,
+        tjd_ut: f64,
+        occulted: &BodyOrStar,
+        iflag: CalcFlags,
+    ) -> Result<handler_swe07::OccultWhereResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::lun_occult_where(tjd_ut, occulted, iflag)
+        })
+    }
+
+    /// See handler_swe07::rise_trans
+    #[allow(clippy::too_many_arguments)]
+    pub fn rise_trans(
+        &self,
+        tjd_ut: f64,
+        body: &BodyOrStar,
+        iflag: CalcFlags,
+        rsmi: RiseTransFlags,
+        geo: GeoPosition,
+        atpress: f64,
+        attemp: f64,
+    ) -> Result<Option<handler_swe07::RiseTransResult>, SweError> {
+        self.with_observer(|| {
+            handler_swe07::rise_trans(
+                tjd_ut, body, iflag, rsmi, geo, atpress, attemp,
+            )
+        })
+    }
+
+    /// See handler_swe07::rise_trans_true_hor
+    #[allow(clippy::too_many_arguments)]
+    pub fn rise_trans_true_hor(
+        &self,
+        tjd_ut: f64,
+        body: &BodyOrStar,
+        iflag: CalcFlags,
+        rsmi: RiseTransFlags,
+        geo: GeoPosition,
+        atpress: f64,
+        attemp: f64,
+        horhgt: f64,
+    ) -> Result<Option<handler_swe07::RiseTransResult>, SweError> {
+        self.with_observer(|| {
+            handler_swe07::rise_trans_true_hor(
+                tjd_ut, body, iflag, rsmi, geo, atpress, attemp, horhgt,
+            )
+        })
+    }
+
+    /// See handler_swe07::twilight
+    pub fn twilight(
+        &self,
+        tjd_ut: f64,
+        iflag: CalcFlags,
+        geo: GeoPosition,
+        atpress: f64,
+        attemp: f64,
+    ) -> Result<handler_swe07::TwilightResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::twilight(tjd_ut, iflag, geo, atpress, attemp)
+        })
+    }
+
+    /// See handler_swe07::heliacal_ut
+    #[allow(clippy::too_many_arguments)]
+    pub fn heliacal_ut(
+        &self,
+        tjd_start: f64,
+        geo: GeoPosition,
+        atm: Atmosphere,
+        obs: Observer,
+        object: &BodyOrStar,
+        event: HeliacalEvent,
+        iflag: CalcFlags,
+        helflag: HeliacalFlags,
+    ) -> Result<handler_swe07::HeliacalResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::heliacal_ut(
+                tjd_start, geo, atm, obs, object, event, iflag, helflag,
+            )
+        })
+    }
+
+    /// See handler_swe07::heliacal_pheno_ut
+    #[allow(clippy::too_many_arguments)]
+    pub fn heliacal_pheno_ut(
+        &self,
+        tjd_ut: f64,
+        geo: GeoPosition,
+        atm: Atmosphere,
+        obs: Observer,
+        object: &BodyOrStar,
+        event: HeliacalEvent,
+        iflag: CalcFlags,
+        helflag: HeliacalFlags,
+    ) -> Result<handler_swe07::HeliacalPhenoResult, SweError> {
+        self.with_observer(|| {
+            handler_swe07::heliacal_pheno_ut(
+                tjd_ut, geo, atm, obs, object, event, iflag, helflag,
+            )
+        })
+    }
+
+    /// See handler_swe07::vis_limit_mag
+    #[allow(clippy::too_many_arguments)]
+    pub fn vis_limit_mag(
+        &self,
+        tjd_ut: f64,
+        geo: GeoPosition,
+        atm: Atmosphere,
+        obs: Observer,
+        object: &BodyOrStar,
+        iflag: CalcFlags,
+        helflag: HeliacalFlags,
+    ) -> Result<Option<handler_swe07::VisLimitMagResult>, SweError> {
+        self.with_observer(|| {
+            handler_swe07::vis_limit_mag(
+                tjd_ut, geo, atm, obs, object, iflag, helflag,
+            )
+        })
+    }
+
+    /// See horizon::azalt
+    #[allow(clippy::too_many_arguments)]
+    pub fn azalt(
+        &self,
+        tjd_ut: f64,
+        coord: CoordinateSystem,
+        geo: GeoPosition,
+        atpress: f64,
+        attemp: f64,
+        longitude: f64,
+        latitude: f64,
+    ) -> horizon::HorizonResult {
+        self.run(|| {
+            horizon::azalt(
+                tjd_ut, coord, geo, atpress, attemp, longitude, latitude,
+            )
+        })
+    }
+
+    /// See horizon::azalt_rev
+    pub fn azalt_rev(
+        &self,
+        tjd_ut: f64,
+        coord: CoordinateSystem,
+        geo: GeoPosition,
+        azimuth: f64,
+        true_altitude: f64,
+    ) -> horizon::AzaltRevResult {
+        self.run(|| {
+            horizon::azalt_rev(tjd_ut, coord, geo, azimuth, true_altitude)
+        })
+    }
+
+    /// See horizon::body_azalt (the observer of set_topo is used with
+    /// OptionalFlag::TopocentricPosition)
+    pub fn body_azalt(
+        &self,
+        tjd_ut: f64,
+        ipl: Bodies,
+        iflag: CalcFlags,
+        geo: GeoPosition,
+        atpress: f64,
+        attemp: f64,
+    ) -> Result<horizon::HorizonResult, SweError> {
+        self.run(|| {
+            horizon::body_azalt(tjd_ut, ipl, iflag, geo, atpress, attemp)
+        })
+    }
+
+    /// See handler_swe08::utc_to_jd (leap seconds file in ephemeris path)
+    #[allow(clippy::too_many_arguments)]
+    pub fn utc_to_jd(
+        &self,
+        year: i32,
+        month: i32,
+        day: i32,
+        hour: i32,
+        min: i32,
+        sec: f64,
+        calandar: Calandar,
+    ) -> Result<handler_swe08::UtcToJdResult, SweError> {
+        self.run(|| {
+            handler_swe08::utc_to_jd(year, month, day, hour, min, sec, calandar)
+        })
+    }
+
+    /// See handler_swe08::jdet_to_utc (leap seconds file in ephemeris path)
+    pub fn jdet_to_utc(
+        &self,
+        tjd_et: f64,
+        calandar: Calandar,
+    ) -> handler_swe08::UtcDateTime {
+        self.run(|| handler_swe08::jdet_to_utc(tjd_et, calandar))
+    }
+
+    /// See handler_swe08::jdut1_to_utc (leap seconds file in ephemeris path)
+    pub fn jdut1_to_utc(
+        &self,
+        tjd_ut: f64,
+        calandar: Calandar,
+    ) -> handler_swe08::UtcDateTime {
+        self.run(|| handler_swe08::jdut1_to_utc(tjd_ut, calandar))
+    }
+
+    /// See handler_swe08::time_equ
+    pub fn time_equ(&self, tjd_ut: f64) -> Result<f64, SweError> {
+        self.run(|| handler_swe08::time_equ(tjd_ut))
+    }
+
+    /// See handler_swe08::lmt_to_lat
+    pub fn lmt_to_lat(
+        &self,
+        tjd_lmt: f64,
+        geolon: f64,
+    ) -> Result<f64, SweError> {
+        self.run(|| handler_swe08::lmt_to_lat(tjd_lmt, geolon))
+    }
+
+    /// See handler_swe08::lat_to_lmt
+    pub fn lat_to_lmt(
+        &self,
+        tjd_lat: f64,
+        geolon: f64,
+    ) -> Result<f64, SweError> {
+        self.run(|| handler_swe08::lat_to_lmt(tjd_lat, geolon))
+    }
+
+    /// See handler_swe08::lat_to_ut
+    pub fn lat_to_ut(
+        &self,
+        tjd_lat: f64,
+        geolon: f64,
+    ) -> Result<f64, SweError> {
+        self.run(|| handler_swe08::lat_to_ut(tjd_lat, geolon))
+    }
+
+    /// See handler_swe08::deltat
+    pub fn deltat(&self, tjd: f64) -> f64 {
+        self.run(|| handler_swe08::deltat(tjd))
+    }
+
+    /// See handler_swe08::deltat_ex
+    pub fn deltat_ex(
+        &self,
+        tjd: f64,
+        iflag: CalcFlags,
+    ) -> Result<handler_swe08::DeltatResult, SweError> {
+        self.run(|| handler_swe08::deltat_ex(tjd, iflag))
+    }
+
+    /// See handler_swe08::get_tid_acc
+    pub fn get_tid_acc(&self) -> f64 {
+        self.run(handler_swe08::get_tid_acc)
+    }
+
+    /// See handler_swe14::houses
+    pub fn houses(
+        &self,
+        tjd_ut: f64,
+        geolat: f64,
+        geolong: f64,
+        hsys: HouseSystem,
+        iflag: CalcFlags,
+    ) -> Result<handler_swe14::HousesResult, SweError> {
+        self.run(|| handler_swe14::houses(tjd_ut, geolat, geolong, hsys, iflag))
+    }
+
+    /// See handler_swe14::houses_armc
+    pub fn houses_armc(
+        &self,
+        armc: f64,
+        geolat: f64,
+        eps: f64,
+        hsys: HouseSystem,
+    ) -> Result<handler_swe14::HousesResult, SweError> {
+        self.run(|| handler_swe14::houses_armc(armc, geolat, eps, hsys))
+    }
+
+    /// See handler_swe15::objects_house_pos
+    pub fn objects_house_pos(
+        &self,
+        tjd_ut: f64,
+        geolat: f64,
+        geolong: f64,
+        hsys: HouseSystem,
+        iflag: CalcFlags,
+        objects: &[Object],
+    ) -> Result<Vec<f64>, SweError> {
+        self.run(|| {
+            handler_swe15::objects_house_pos(
+                tjd_ut, geolat, geolong, hsys, iflag, objects,
+            )
+        })
+    }
+
+    /// See handler_swe15::gauquelin_sector
+    #[allow(clippy::too_many_arguments)]
+    pub fn gauquelin_sector(
+        &self,
+        tjd_ut: f64,
+        body: &BodyOrStar,
+        iflag: CalcFlags,
+        imeth: GauquelinMethod,
+        geo: GeoPosition,
+        atpress: f64,
+        attemp: f64,
+    ) -> Result<f64, SweError> {
+        self.with_observer(|| {
+            handler_swe15::gauquelin_sector(
+                tjd_ut, body, iflag, imeth, geo, atpress, attemp,
+            )
+        })
+    }
+
+    /// See handler_swe16::sidtime
+    pub fn sidtime(&self, tjd_ut: f64) -> f64 {
+        self.run(|| handler_swe16::sidtime(tjd_ut))
+    }
+
+    /// See handler_swe16::local_sidtime
+    pub fn local_sidtime(&self, tjd_ut: f64, geolong: f64) -> f64 {
+        self.run(|| handler_swe16::local_sidtime(tjd_ut, geolong))
+    }
+
+    /// See handler_swe16::armc
+    pub fn armc(&self, tjd_ut: f64, geolong: f64) -> f64 {
+        self.run(|| handler_swe16::armc(tjd_ut, geolong))
+    }
+
+    /// See handler_swe16::local_sidtime_to_ut
+    pub fn local_sidtime_to_ut(
+        &self,
+        tjd_ut: f64,
+        geolong: f64,
+        lst: f64,
+    ) -> f64 {
+        self.run(|| handler_swe16::local_sidtime_to_ut(tjd_ut, geolong, lst))
+    }
+
+    /// See handler_swe11::get_ayanamsa_ex_ut
+    pub fn get_ayanamsa_ex_ut(
+        &self,
+        tjd_ut: f64,
+        iflag: CalcFlags,
+    ) -> Result<f64, SweError> {
+        self.run(|| handler_swe11::get_ayanamsa_ex_ut(tjd_ut, iflag))
+    }
+
+    /// See handler_swe11::get_ayanamsa_ex
+    pub fn get_ayanamsa_ex(
+        &self,
+        tjd_et: f64,
+        iflag: CalcFlags,
+    ) -> Result<f64, SweError> {
+        self.run(|| handler_swe11::get_ayanamsa_ex(tjd_et, iflag))
+    }
+
+    /// Apply the configuration in the c library if needed
+    fn apply_if_needed(&self) {
+        if state::applied() != Some(self.id) {
+            self.apply();
+            state::set_applied(Some(self.id));
+        }
+    }
+
+    /// Apply the configuration of this session in the c library
+    fn apply(&self) {
+        unsafe {
+            // Forget the configuration of the previous session
+            raw::swe_close();
+            raw::swe_set_ephe_path(
+                self.path.as_deref().map_or(ptr::null(), CStr::as_ptr),
+            );
+            raw::swe_set_jpl_file(self.jpl_file.as_deref().map_or(
+                JPL_FILE_DEFAULT.as_ptr() as *const c_char,
+                CStr::as_ptr,
+            ));
+        }
+        if let Some((ayanamsa, t0, ayan_t0)) = self.sid_mode {
+            handler_swe11::set_sid_mode(ayanamsa, t0, ayan_t0);
+        }
+        if let Some(geo) = self.topo {
+            handler_swe10::set_topo(geo);
+        }
+        handler_swe08::set_delta_t_userdef(self.delta_t_userdef);
+        handler_swe08::set_tid_acc(self.tid_acc);
+        // Not reset by swe_close
+        horizon::set_lapse_rate(self.lapse_rate);
+    }
+
+    /// star_path;ephe_path, max 255 char for the c library
+    fn set_path(
+        &mut self,
+        ephe_path: Option<String>,
+        star_path: Option<String>,
+    ) -> Result<(), SweError> {
+        let path: Vec<&str> = star_path
+            .iter()
+            .chain(ephe_path.iter())
+            .map(|p| p.as_str())
+            .collect();
+        self.path = if path.is_empty() {
+            None
+        } else {
+            // ";" is the path separator on all the platforms
+            Some(sweerror::to_c_string(&path.join(";"))?)
+        };
+        self.ephe_path = ephe_path;
+        self.star_path = star_path;
+        self.reset();
+        Ok(())
+    }
+
+    /// The configuration has changed, new id to apply it again
+    fn reset(&mut self) {
+        self.id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Ephemeris {
+    /// Close swiss ephemeris, free memory, if the configuration of this
+    /// session is still in the c library (otherwise the session applied
+    /// after has already closed the files of this one)
+    fn drop(&mut self) {
+        let _guard = state::lock();
+        if state::applied() == Some(self.id) {
+            handler_swe02::close();
+            state::set_applied(None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweconst::OptionalFlag;
+    use std::thread;
+
+    const J2000: f64 = 2_451_545.0;
+
+    fn ayanamsa(ephemeris: &Ephemeris) -> f64 {
+        ephemeris
+            .get_ayanamsa_ex_ut(J2000, CalcFlags::new())
+            .unwrap()
+    }
+
+    fn session(ayanamsa: Ayanamsa) -> Ephemeris {
+        let mut ephemeris = Ephemeris::default();
+        ephemeris.set_sid_mode(ayanamsa, 0.0, 0.0);
+        ephemeris
+    }
+
+    #[test]
+    fn sessions_in_threads() {
+        let expected = |mode: Ayanamsa| ayanamsa(&session(mode));
+        let (lahiri, fagan_bradley) =
+            (expected(Ayanamsa::Lahiri), expected(Ayanamsa::FaganBradley));
+        assert!((lahiri - fagan_bradley).abs() > 0.1);
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                thread::spawn(move || {
+                    let (mode, expected) = if i % 2 == 0 {
+                        (Ayanamsa::Lahiri, lahiri)
+                    } else {
+                        (Ayanamsa::FaganBradley, fagan_bradley)
+                    };
+                    let ephemeris = session(mode);
+                    for _ in 0..200 {
+                        assert_eq!(ayanamsa(&ephemeris), expected);
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn sessions_in_with() {
+        let lahiri = session(Ayanamsa::Lahiri);
+        let fagan_bradley = session(Ayanamsa::FaganBradley);
+        let expected = ayanamsa(&lahiri);
+        let (in_with, other, after_other) = lahiri.with(|| {
+            (
+                ayanamsa(&lahiri),
+                ayanamsa(&fagan_bradley),
+                ayanamsa(&lahiri),
+            )
+        });
+        assert_eq!(in_with, expected);
+        assert_ne!(other, expected);
+        assert_eq!(after_other, expected);
+        // handler_swe11::set_sid_mode in with is forgotten afterwards
+        lahiri.with(|| handler_swe11::set_sid_mode(Ayanamsa::Raman, 0.0, 0.0));
+        assert_eq!(ayanamsa(&lahiri), expected);
+    }
+
+    #[test]
+    fn calc_ut_topo_restores_observer() {
+        let moon = |ephemeris: &Ephemeris| {
+            ephemeris
+                .calc_ut(
+                    J2000,
+                    Bodies::Moon,
+                    OptionalFlag::TopocentricPosition.into(),
+                )
+                .unwrap()
+                .longitude
+        };
+        let mut ephemeris = Ephemeris::default();
+        ephemeris.set_topo(GeoPosition::new(6.15, 46.2, 400.0));
+        let expected = moon(&ephemeris);
+        let other = ephemeris
+            .calc_ut_topo(
+                J2000,
+                Bodies::Moon,
+                CalcFlags::new(),
+                GeoPosition::new(-122.4, -37.8, 0.0),
+            )
+            .unwrap()
+            .longitude;
+        assert!((other - expected).abs() > 0.1);
+        assert_eq!(moon(&ephemeris), expected);
+    }
+}
@@ -1,13 +1,29 @@
+mod ephemeris;
 mod swe02;
 mod swe03;
+mod swe05;
+mod swe06;
 mod swe07;
 mod swe08;
+mod swe10;
+mod swe11;
 mod swe14;
+mod swe15;
+mod swe16;
 mod swe17;
 
+pub use self::ephemeris::Ephemeris;
 pub use self::swe02::handler as handler_swe02;
 pub use self::swe03::handler as handler_swe03;
+pub use self::swe05::handler as handler_swe05;
+pub use self::swe06::handler as handler_swe06;
 pub use self::swe07::handler as handler_swe07;
+pub use self::swe07::horizon;
 pub use self::swe08::handler as handler_swe08;
+pub use self::swe10::handler as handler_swe10;
+pub use self::swe11::handler as handler_swe11;
 pub use self::swe14::handler as handler_swe14;
+pub use self::swe15::handler as handler_swe15;
+pub use self::swe16::handler as handler_swe16;
+pub use self::swe17::coords;
 pub use self::swe17::handler as handler_swe17;
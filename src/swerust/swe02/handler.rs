@@ -1,5 +1,5 @@
 use crate::raw;
-use std::ffi::{CStr, CString};
+use crate::sweerror::{self, SweError};
 use std::os::raw::c_char;
 
 /*
@@ -7,15 +7,13 @@ use std::os::raw::c_char;
  */
 
 /// Set the path of ephemeris
-pub fn set_ephe_path(path: &str) {
-    if path.len() > 255 {
-        panic!("swe 2.1 -> set_ephe_path -> path to long");
-    }
-    let c_str = CString::new(path).unwrap();
+pub fn set_ephe_path(path: &str) -> Result<(), SweError> {
+    let c_str = sweerror::to_c_string(path)?;
     let path_final: *const c_char = c_str.as_ptr() as *const c_char;
     unsafe {
         raw::swe_set_ephe_path(path_final);
     }
+    Ok(())
 }
 
 /// Close swiss ephemeris, free memory
@@ -24,37 +22,31 @@ pub fn close() {
 }
 
 /// Set the path of ephemeris for working with JPL file
-pub fn set_jpl_file(fname: &str) {
-    if fname.len() > 255 {
-        panic!("swe 2.3 -> set_jpl_file -> fname to long");
-    }
-    let c_str = CString::new(fname).unwrap();
+pub fn set_jpl_file(fname: &str) -> Result<(), SweError> {
+    let c_str = sweerror::to_c_string(fname)?;
     let fname_final: *const c_char = c_str.as_ptr() as *const c_char;
     unsafe {
         raw::swe_set_jpl_file(fname_final);
     }
+    Ok(())
 }
 
 /// Get version of swiss ephemeris
-pub fn version() -> String {
+pub fn version() -> Result<String, SweError> {
     // Get the version
-    let mut version = [0; 255];
-    let v = unsafe {
-        let p = version.as_mut_ptr();
-        raw::swe_version(p);
-        CStr::from_ptr(p)
-    };
-    CString::from(v).to_str().unwrap().to_string()
+    let mut version = [0; sweerror::SERR_LEN];
+    unsafe {
+        raw::swe_version(version.as_mut_ptr());
+    }
+    sweerror::c_buf_to_string(&version)
 }
 
 /// Get librarx path dll
-pub fn get_library_path() -> String {
+pub fn get_library_path() -> Result<String, SweError> {
     // Get dll path
-    let mut dll_path = [0; 255];
-    let dll = unsafe {
-        let p = dll_path.as_mut_ptr();
-        raw::swe_get_library_path(p);
-        CStr::from_ptr(p)
-    };
-    CString::from(dll).to_str().unwrap().to_string()
+    let mut dll_path = [0; sweerror::SERR_LEN];
+    unsafe {
+        raw::swe_get_library_path(dll_path.as_mut_ptr());
+    }
+    sweerror::c_buf_to_string(&dll_path)
 }
@@ -1,7 +1,10 @@
 use crate::raw;
-use crate::sweconst::Bodies;
+use crate::sweconst::{
+    Bodies, CalcFlags, GeoPosition, HouseSystem, OptionalFlag,
+};
+use crate::sweerror::{self, Call, SweError};
 use crate::swerust;
-use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_double, c_int};
 
 /*
  * 3. The functions swe_calc_ut() and swe_calc()
@@ -33,230 +36,163 @@ pub struct CalcUtResult {
     pub speed_longitude: f64,
     pub speed_latitude: f64,
     pub speed_distance_au: f64,
-    pub status: i32,
-    pub serr: String,
+    /// Some(SweError::MoshierFallback) if the ephemeris asked in iflag has
+    /// not been found, the position is computed with Moshier
+    pub warning: Option<SweError>,
 }
 
-pub fn calc_ut(tjd_ut: f64, ipl: Bodies, iflag: i32) -> CalcUtResult {
-    let mut xx: [f64; 6] = [0.0; 6];
-    let mut serr = [0; 255];
-    let result;
-    result = unsafe {
-        let p_xx = xx.as_mut_ptr();
-        let p_serr = serr.as_mut_ptr();
-        let status;
-        if ipl == Bodies::SouthNode {
-            status = raw::swe_calc_ut(
-                tjd_ut,
-                Bodies::TrueNode as i32,
-                iflag,
-                p_xx,
-                p_serr,
-            );
-        } else {
-            status = raw::swe_calc_ut(tjd_ut, ipl as i32, iflag, p_xx, p_serr);
-        }
-        let s_serr = CString::from(CStr::from_ptr(p_serr))
-            .to_str()
-            .unwrap()
-            .to_string();
-        if ipl == Bodies::SouthNode {
-            xx[0] += 180.0;
-            if xx[0] >= 360.0 {
-                xx[0] -= 360.0;
-            }
-        }
-        CalcUtResult {
-            longitude: xx[0],
-            latitude: xx[1],
-            distance_au: xx[2],
-            speed_longitude: xx[3],
-            speed_latitude: xx[4],
-            speed_distance_au: xx[5],
-            serr: s_serr,
-            status,
-        }
-    };
-    result
-}
-
-/// Fortuna Part
-/// Only lng is valid, the speed is unknow because this object is calculated
-pub fn calc_ut_fp(
+/// Position at the julian day in Universal Time
+pub fn calc_ut(
     tjd_ut: f64,
-    geolat: f64,
-    geolong: f64,
-    hsys: char,
-    iflag: i32,
-) -> CalcUtResult {
-    let ipl = Bodies::FortunaPart;
-    let mut xx: [f64; 6] = [0.0; 6];
-    let mut serr = [0; 255];
-    let result = unsafe {
-        let p_xx = xx.as_mut_ptr();
-        let p_serr = serr.as_mut_ptr();
-        let status = raw::swe_calc_ut(tjd_ut, ipl as i32, iflag, p_xx, p_serr);
-        let s_serr = CString::from(CStr::from_ptr(p_serr))
-            .to_str()
-            .unwrap()
-            .to_string();
-        let mut xx_sun: [f64; 6] = [0.0; 6];
-        let mut xx_moon: [f64; 6] = [0.0; 6];
-        let p_xx_sun = xx_sun.as_mut_ptr();
-        let p_serr_sun = serr.as_mut_ptr();
-        let _status_sun = raw::swe_calc_ut(
-            tjd_ut,
-            Bodies::Sun as i32,
-            iflag,
-            p_xx_sun,
-            p_serr_sun,
-        );
-        let p_xx_moon = xx_moon.as_mut_ptr();
-        let p_serr_moon = serr.as_mut_ptr();
-        let _status_moon = raw::swe_calc_ut(
-            tjd_ut,
-            Bodies::Moon as i32,
-            iflag,
-            p_xx_moon,
-            p_serr_moon,
-        );
-        let _s_serr_sun = CString::from(CStr::from_ptr(p_serr_sun))
-            .to_str()
-            .unwrap()
-            .to_string();
-        let _s_serr_moon = CString::from(CStr::from_ptr(p_serr_moon))
-            .to_str()
-            .unwrap()
-            .to_string();
-        let result_houses =
-            swerust::handler_swe14::houses(tjd_ut, geolat, geolong, hsys);
-        let asc_lon = result_houses.cusps[1];
-        let mc_lon = result_houses.cusps[10];
-        let mc_lat = 0.0;
-        let compute_sun = eq_coords(xx_sun[0], xx_sun[1]);
-        let compute_mc = eq_coords(mc_lon, mc_lat);
-        let sw_is_diurnal = is_above_horizon(
-            compute_sun.0,
-            compute_sun.1,
-            compute_mc.0,
-            compute_mc.1,
-        );
-        /*println!(
-            "sw_is_diurnal: {}asc: {}moon: {}sun: {}",
-            sw_is_diurnal.clone(),
-            asc_lon.clone(),
-            xx_moon[0].clone(),
-            xx_sun[0].clone()
-        );*/
-        let mut lon = if sw_is_diurnal {
-            asc_lon + xx_moon[0] - xx_sun[0]
-        } else {
-            asc_lon + xx_sun[0] - xx_moon[0]
-        };
-        let mut done = false;
-        while !done {
-            if lon < 0.0 {
-                lon += 360.0;
-            } else {
-                done = true;
-            }
-        }
-        done = false;
-        while !done {
-            if lon >= 360.0 {
-                lon -= 360.0;
-            } else {
-                done = true;
-            }
-        }
-        CalcUtResult {
-            longitude: lon,
-            latitude: xx[1],
-            distance_au: xx[2],
-            speed_longitude: xx[3],
-            speed_latitude: xx[4],
-            speed_distance_au: xx[5],
-            serr: s_serr,
-            status,
-        }
-    };
-    result
+    ipl: Bodies,
+    iflag: CalcFlags,
+) -> Result<CalcUtResult, SweError> {
+    calc_body(raw::swe_calc_ut, tjd_ut, ipl, iflag)
 }
 
-/// Converts from ecliptical to equatorial coordinates.
-fn eq_coords(lon: f64, lat: f64) -> (f64, f64) {
-    // Convert to radian
-    let lambda = lon.to_radians();
-    let beta = lat.to_radians();
-    let epson = (23.44_f64).to_radians(); // the earth inclinaison
-
-    // Declinaison in radian
-    let decl = (epson.sin() * lambda.sin() * beta.cos()
-        + epson.cos() * beta.sin())
-    .asin();
+/// Position at the julian day in Terrestrial Time (ephemeris time)
+///
+/// tjd_et = tjd_ut + handler_swe08::deltat(tjd_ut)
+pub fn calc(
+    tjd_et: f64,
+    ipl: Bodies,
+    iflag: CalcFlags,
+) -> Result<CalcUtResult, SweError> {
+    calc_body(raw::swe_calc, tjd_et, ipl, iflag)
+}
 
-    // Equatorial distance
-    let ed = (lambda.cos() * beta.cos() / decl.cos()).acos();
+/// raw::swe_calc_ut or raw::swe_calc
+type SweCalc = unsafe extern "C" fn(
+    c_double,
+    c_int,
+    c_int,
+    *mut c_double,
+    *mut c_char,
+) -> c_int;
 
-    // RA in radian
-    let mut ra = if lon < 100.0 {
-        ed
+fn calc_body(
+    swe_calc: SweCalc,
+    tjd: f64,
+    ipl: Bodies,
+    iflag: CalcFlags,
+) -> Result<CalcUtResult, SweError> {
+    let iflag = iflag.validate()?.bits();
+    let mut xx: [f64; 6] = [0.0; 6];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let ipl_c = if ipl == Bodies::SouthNode {
+        Bodies::TrueNode
     } else {
-        (360.0_f64).to_radians() - ed
+        ipl
+    }
+    .calc_ipl()?;
+    let status = unsafe {
+        swe_calc(tjd, ipl_c, iflag, xx.as_mut_ptr(), serr.as_mut_ptr())
     };
-
-    // Correctness of RA if longitude is close to 0° or 180° in a radius of 5°
-    if (closest_distance(lon, 0.0)).abs() < 5.0
-        || (closest_distance(lon, 180.0)).abs() < 5.0
-    {
-        let a = ra.sin() * decl.cos();
-        let b =
-            epson.cos() * lambda.sin() * beta.cos() - epson.sin() * beta.sin();
-        if (a - b).abs() > 0.0003 {
-            ra = (360.0_f64).to_radians() - ra;
+    sweerror::check_status(
+        status,
+        &serr,
+        Call::Body {
+            ipl: ipl_c,
+            tjd,
+            iflag,
+        },
+    )?;
+    if ipl == Bodies::SouthNode {
+        xx[0] += 180.0;
+        if xx[0] >= 360.0 {
+            xx[0] -= 360.0;
         }
     }
-    (ra.to_degrees(), decl.to_degrees())
+    Ok(CalcUtResult {
+        longitude: xx[0],
+        latitude: xx[1],
+        distance_au: xx[2],
+        speed_longitude: xx[3],
+        speed_latitude: xx[4],
+        speed_distance_au: xx[5],
+        warning: sweerror::moshier_fallback(iflag, status, &serr),
+    })
 }
 
-/// Boolean if is above horizon
-/// Returns if an object's ra and decl is above the horizon at a specific
-/// latitude, given the MC's right ascension.
+/// Topocentric position for an observer, for Ephemeris::calc_ut_topo
 ///
-/// This function checks if the equatorial distance from the object to the MC
-/// is within its diurnal semi-arc.
-fn is_above_horizon(ra: f64, decl: f64, mc_ra: f64, lat: f64) -> bool {
-    let d_arc_tulpe = dnarcs(decl, lat);
-    let dist = (closest_distance(mc_ra, ra)).abs();
-    dist <= d_arc_tulpe.0 / 2.0 + 0.0003 // 1 arc-second
-}
-
-/// Returns the diurnal and nocturnal arcs of a point.
-fn dnarcs(decl: f64, lat: f64) -> (f64, f64) {
-    let d_arc = 180.0 + 2.0 * ascdiff(decl, lat);
-    let n_arc = 360.0 - d_arc;
-    (d_arc, n_arc)
-}
-
-/// Returns the Ascensional Difference of a point.
-fn ascdiff(decl: f64, lat: f64) -> f64 {
-    let delta = decl.to_radians();
-    let phi = lat.to_radians();
-    let ad = (delta.tan() * phi.tan()).asin();
-    ad.to_degrees()
-}
-
-/// Closest distance between 2 point
-fn closest_distance(angle1: f64, angle2: f64) -> f64 {
-    znorm(angle2 - angle1)
+/// swe_set_topo is called with geo and TopocentricPosition is added to iflag,
+/// the session sets its own observer again after the call
+pub(crate) fn calc_ut_topo(
+    tjd_ut: f64,
+    ipl: Bodies,
+    iflag: CalcFlags,
+    geo: GeoPosition,
+) -> Result<CalcUtResult, SweError> {
+    swerust::handler_swe10::set_topo(geo);
+    calc_ut(tjd_ut, ipl, iflag | OptionalFlag::TopocentricPosition)
 }
 
-/// Normalize angle between -180° and 180°
-fn znorm(mut angle: f64) -> f64 {
-    angle %= 360.0;
-    if angle <= 180.0 {
-        angle
+/// Fortuna Part
+/// Only lng is valid, the speed is unknow because this object is calculated:
+/// latitude, distance and speeds are 0.0 (the c library has no position for
+/// Bodies::FortunaPart)
+pub fn calc_ut_fp(
+    tjd_ut: f64,
+    geolat: f64,
+    geolong: f64,
+    hsys: HouseSystem,
+    iflag: CalcFlags,
+) -> Result<CalcUtResult, SweError> {
+    let calc_sun = calc_ut(tjd_ut, Bodies::Sun, iflag)?;
+    let calc_moon = calc_ut(tjd_ut, Bodies::Moon, iflag)?;
+    // Sidereal houses with a sidereal Sun and Moon
+    let iflag_houses = if iflag.contains(OptionalFlag::SideralPosition) {
+        CalcFlags::from(OptionalFlag::SideralPosition)
     } else {
-        angle - 180.0
+        CalcFlags::new()
+    };
+    let result_houses = swerust::handler_swe14::houses(
+        tjd_ut,
+        geolat,
+        geolong,
+        hsys,
+        iflag_houses,
+    )?;
+    let asc_lon = result_houses.ascendant;
+    // Diurnal chart: the Sun is above the horizon
+    let sw_is_diurnal = swerust::horizon::body_azalt(
+        tjd_ut,
+        Bodies::Sun,
+        iflag,
+        GeoPosition::new(geolong, geolat, 0.0),
+        0.0,
+        0.0,
+    )?
+    .is_above_horizon();
+    let mut lon = if sw_is_diurnal {
+        asc_lon + calc_moon.longitude - calc_sun.longitude
+    } else {
+        asc_lon + calc_sun.longitude - calc_moon.longitude
+    };
+    let mut done = false;
+    while !done {
+        if lon < 0.0 {
+            lon += 360.0;
+        } else {
+            done = true;
+        }
+    }
+    done = false;
+    while !done {
+        if lon >= 360.0 {
+            lon -= 360.0;
+        } else {
+            done = true;
+        }
     }
+    Ok(CalcUtResult {
+        longitude: lon,
+        latitude: 0.0,
+        distance_au: 0.0,
+        speed_longitude: 0.0,
+        speed_latitude: 0.0,
+        speed_distance_au: 0.0,
+        warning: calc_sun.warning,
+    })
 }
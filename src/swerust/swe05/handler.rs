@@ -0,0 +1,185 @@
+use crate::raw;
+use crate::sweconst::{CalcFlags, Fixstar};
+use crate::sweerror::{self, Call, SweError};
+use std::os::raw::c_char;
+
+/*
+ * 5. Fixed stars functions
+ *
+ * The stars are read in the file sefstars.txt, searched in the ephemeris
+ * path. For a custom star file, put a sefstars.txt in a directory and add
+ * this directory in front of the ephemeris path ("/my/stars;/my/ephe"), or
+ * use Ephemeris::set_star_path.
+ */
+
+/*
+ * xx
+ *
+ * Same as CalcUtResult (swe_calc_ut)
+ */
+#[derive(Debug)]
+pub struct FixstarResult {
+    /// Traditional name ("Aldebaran")
+    pub name: String,
+    /// Bayer designation ("alTau")
+    pub bayer: String,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub distance_au: f64,
+    pub speed_longitude: f64,
+    pub speed_latitude: f64,
+    pub speed_distance_au: f64,
+    /// Visual magnitude
+    pub magnitude: f64,
+    /// Some(SweError::MoshierFallback) if the ephemeris asked in iflag has
+    /// not been found
+    pub warning: Option<SweError>,
+}
+
+/// Position, speed and magnitude of a fixed star
+pub fn fixstar2_ut(
+    star: &Fixstar,
+    tjd_ut: f64,
+    iflag: CalcFlags,
+) -> Result<FixstarResult, SweError> {
+    let iflag = iflag.validate()?.bits();
+    let mut star_c = sweerror::to_star_buf(&star.search())?;
+    let mut xx: [f64; 6] = [0.0; 6];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_fixstar2_ut(
+            star_c.as_mut_ptr(),
+            tjd_ut,
+            iflag,
+            xx.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, Call::Star)?;
+    let warning = sweerror::moshier_fallback(iflag, status, &serr);
+    // star_c is now "name,bayer": magnitude of the star found, and not a
+    // second search of the string given (the name if there is no Bayer
+    // designation in the file)
+    let (name, bayer) = split_star_name(&star_c)?;
+    let magnitude = if bayer.is_empty() {
+        star_mag(&mut sweerror::to_star_buf(&name)?)?
+    } else {
+        star_mag(&mut star_c)?
+    };
+    Ok(FixstarResult {
+        name,
+        bayer,
+        longitude: xx[0],
+        latitude: xx[1],
+        distance_au: xx[2],
+        speed_longitude: xx[3],
+        speed_latitude: xx[4],
+        speed_distance_au: xx[5],
+        magnitude,
+        warning,
+    })
+}
+
+/// Visual magnitude of a fixed star
+pub fn fixstar2_mag(star: &Fixstar) -> Result<f64, SweError> {
+    star_mag(&mut sweerror::to_star_buf(&star.search())?)
+}
+
+fn star_mag(
+    star_c: &mut [c_char; sweerror::STAR_LEN],
+) -> Result<f64, SweError> {
+    let mut mag = [0.0; 1];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_fixstar2_mag(
+            star_c.as_mut_ptr(),
+            mag.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, Call::Star)?;
+    Ok(mag[0])
+}
+
+/// "Aldebaran,alTau" -> ("Aldebaran", "alTau")
+fn split_star_name(star_c: &[c_char]) -> Result<(String, String), SweError> {
+    let star = sweerror::c_buf_to_string(star_c)?;
+    let mut split = star.splitn(2, ',');
+    let name = split.next().unwrap_or("").trim().to_string();
+    let bayer = split.next().unwrap_or("").trim().to_string();
+    Ok((name, bayer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swerust::Ephemeris;
+    use std::fs;
+
+    const J2000: f64 = 2_451_545.0;
+    // sefstars.txt of the c library
+    const STAR_PATH: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/swisseph/2.08/src");
+
+    fn fixstar(star: Fixstar) -> FixstarResult {
+        let mut ephemeris = Ephemeris::default();
+        ephemeris.set_star_path(STAR_PATH).unwrap();
+        ephemeris
+            .fixstar2_ut(&star, J2000, CalcFlags::new())
+            .unwrap()
+    }
+
+    fn assert_aldebaran(star: &FixstarResult) {
+        assert_eq!(star.name, "Aldebaran");
+        assert_eq!(star.bayer, "alTau");
+        assert!((star.longitude - 69.79).abs() < 0.01);
+        assert_eq!(star.magnitude, 0.86);
+    }
+
+    #[test]
+    fn fixstar_by_name() {
+        assert_aldebaran(&fixstar(Fixstar::Name("aldebaran".to_string())));
+    }
+
+    #[test]
+    fn fixstar_by_bayer() {
+        assert_aldebaran(&fixstar(Fixstar::Bayer("alTau".to_string())));
+    }
+
+    #[test]
+    fn fixstar_by_number() {
+        // The stars are numbered in the order of their Bayer designations
+        let star = fixstar(Fixstar::Number(1));
+        let bayer = fixstar(Fixstar::Bayer("109Vir".to_string()));
+        assert_eq!(star.name, "");
+        assert_eq!(star.bayer, "109Vir");
+        assert_eq!(star.longitude, bayer.longitude);
+        assert_eq!(star.magnitude, 3.73);
+    }
+
+    #[test]
+    fn fixstar_custom_star_path() {
+        let dir = std::env::temp_dir()
+            .join(format!("libswe-sys-stars-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("sefstars.txt"),
+            "Mystar,zzMys,ICRS,04,35,55.23907,+16,30,33.4885,\
+             63.45,-188.94,54.26,48.94,4.2, 16,  629\n",
+        )
+        .unwrap();
+        let mut ephemeris = Ephemeris::default();
+        ephemeris.set_star_path(dir.to_str().unwrap()).unwrap();
+        let star = ephemeris
+            .fixstar2_ut(
+                &Fixstar::Name("Mystar".to_string()),
+                J2000,
+                CalcFlags::new(),
+            )
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(star.name, "Mystar");
+        assert_eq!(star.bayer, "zzMys");
+        assert_eq!(star.magnitude, 4.2);
+    }
+}
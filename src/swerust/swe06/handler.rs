@@ -0,0 +1,324 @@
+use crate::raw;
+use crate::sweconst::{Bodies, CalcFlags, NodApsMethod, OptionalFlag};
+use crate::sweerror::{self, Call, SweError};
+
+/*
+ * 6. Kepler elements, apsides and nodes, orbital periods
+ */
+
+/// SE_NODBIT_FOPOINT, added to the method
+const NODBIT_FOPOINT: i32 = 256;
+
+/// Position of a node or an apsis (same as CalcUtResult)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodApsPosition {
+    pub longitude: f64,
+    pub latitude: f64,
+    pub distance_au: f64,
+    pub speed_longitude: f64,
+    pub speed_latitude: f64,
+    pub speed_distance_au: f64,
+}
+
+impl NodApsPosition {
+    fn new(xx: [f64; 6]) -> NodApsPosition {
+        NodApsPosition {
+            longitude: xx[0],
+            latitude: xx[1],
+            distance_au: xx[2],
+            speed_longitude: xx[3],
+            speed_latitude: xx[4],
+            speed_distance_au: xx[5],
+        }
+    }
+}
+
+/// Nodes and apsides of a planet (swe_nod_aps_ut)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodApsResult {
+    pub ascending_node: NodApsPosition,
+    pub descending_node: NodApsPosition,
+    /// Perigee for the Moon
+    pub perihelion: NodApsPosition,
+    /// Apogee for the Moon, second focal point of the orbit with
+    /// focal_point
+    pub aphelion: NodApsPosition,
+}
+
+/// Nodes and apsides of a planet at the julian day in UT
+///
+/// iflag: same as handler_swe03::calc_ut (ephemeris, heliocentric, speed,
+/// ...). focal_point: the second focal point of the orbital ellipse
+/// instead of the aphelion.
+pub fn nod_aps_ut(
+    tjd_ut: f64,
+    ipl: Bodies,
+    iflag: CalcFlags,
+    method: NodApsMethod,
+    focal_point: bool,
+) -> Result<NodApsResult, SweError> {
+    let iflag = iflag.validate()?.bits();
+    let ipl = ipl.calc_ipl()?;
+    let method = if focal_point {
+        method as i32 | NODBIT_FOPOINT
+    } else {
+        method as i32
+    };
+    let mut xnasc = [0.0; 6];
+    let mut xndsc = [0.0; 6];
+    let mut xperi = [0.0; 6];
+    let mut xaphe = [0.0; 6];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_nod_aps_ut(
+            tjd_ut,
+            ipl,
+            iflag,
+            method,
+            xnasc.as_mut_ptr(),
+            xndsc.as_mut_ptr(),
+            xperi.as_mut_ptr(),
+            xaphe.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(
+        status,
+        &serr,
+        Call::Body {
+            ipl,
+            tjd: tjd_ut,
+            iflag,
+        },
+    )?;
+    Ok(NodApsResult {
+        ascending_node: NodApsPosition::new(xnasc),
+        descending_node: NodApsPosition::new(xndsc),
+        perihelion: NodApsPosition::new(xperi),
+        aphelion: NodApsPosition::new(xaphe),
+    })
+}
+
+/// Osculating orbital elements (Kepler elements) of a body
+/// (swe_get_orbital_elements) and its distances
+/// (swe_orbit_max_min_true_distance)
+///
+/// Relative to the mean ecliptic J2000, angles in degrees, distances in AU
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitalElementsResult {
+    pub semimajor_axis: f64,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    /// Longitude of the ascending node
+    pub ascending_node: f64,
+    /// Argument of the perihelion
+    pub argument_of_perihelion: f64,
+    /// Longitude of the perihelion
+    pub longitude_of_perihelion: f64,
+    /// Mean anomaly at epoch
+    pub mean_anomaly: f64,
+    /// True anomaly at epoch
+    pub true_anomaly: f64,
+    /// Eccentric anomaly at epoch
+    pub eccentric_anomaly: f64,
+    /// Mean longitude at epoch
+    pub mean_longitude: f64,
+    /// Sidereal orbital period in tropical years
+    pub sidereal_period: f64,
+    /// Mean daily motion
+    pub daily_motion: f64,
+    /// Tropical period in years
+    pub tropical_period: f64,
+    /// Synodic period in days, negative for the inner planets and the Moon
+    pub synodic_period: f64,
+    /// Time of the perihelion passage (julian day in ET)
+    pub perihelion_passage: f64,
+    pub perihelion_distance: f64,
+    pub aphelion_distance: f64,
+    /// Distances in the frame of the elements (geocentric for the Moon)
+    pub distance: OrbitDistanceResult,
+}
+
+/// Maximum, minimum and true distance of a body in AU
+/// (swe_orbit_max_min_true_distance)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitDistanceResult {
+    pub max_distance: f64,
+    pub min_distance: f64,
+    pub true_distance: f64,
+}
+
+/// Orbital elements of a planet, the Earth-Moon barycenter or an asteroid
+/// at the julian day in ET (Err for the Sun, the lunar nodes and apsides)
+///
+/// iflag: the ephemeris, OptionalFlag::BarycentricPosition for the
+/// planets beyond Jupiter (heliocentric otherwise)
+///
+/// distance is in the frame of the elements: geocentric for the Moon,
+/// barycentric or heliocentric like the elements otherwise (the other flags
+/// of iflag are ignored)
+pub fn get_orbital_elements(
+    tjd_et: f64,
+    ipl: Bodies,
+    iflag: CalcFlags,
+) -> Result<OrbitalElementsResult, SweError> {
+    let iflag = iflag.validate()?;
+    let ipl_c = ipl.orbit_ipl()?;
+    let mut dret = [0.0; 50];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_get_orbital_elements(
+            tjd_et,
+            ipl_c,
+            iflag.bits(),
+            dret.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(
+        status,
+        &serr,
+        Call::Body {
+            ipl: ipl_c,
+            tjd: tjd_et,
+            iflag: iflag.bits(),
+        },
+    )?;
+    // Same frame as the elements, the c library chooses between
+    // barycentric and heliocentric (beyond Jupiter) the same way
+    let iflag_distance = if ipl == Bodies::Moon {
+        iflag.ephemeris()
+    } else if iflag.contains(OptionalFlag::BarycentricPosition) {
+        iflag.ephemeris().with(OptionalFlag::BarycentricPosition)
+    } else {
+        iflag.ephemeris().with(OptionalFlag::Heliocentric)
+    };
+    let distance = orbit_max_min_true_distance(tjd_et, ipl, iflag_distance)?;
+    Ok(OrbitalElementsResult {
+        semimajor_axis: dret[0],
+        eccentricity: dret[1],
+        inclination: dret[2],
+        ascending_node: dret[3],
+        argument_of_perihelion: dret[4],
+        longitude_of_perihelion: dret[5],
+        mean_anomaly: dret[6],
+        true_anomaly: dret[7],
+        eccentric_anomaly: dret[8],
+        mean_longitude: dret[9],
+        sidereal_period: dret[10],
+        daily_motion: dret[11],
+        tropical_period: dret[12],
+        synodic_period: dret[13],
+        perihelion_passage: dret[14],
+        perihelion_distance: dret[15],
+        aphelion_distance: dret[16],
+        distance,
+    })
+}
+
+/// Maximum, minimum and true distance of a planet, the Earth-Moon
+/// barycenter or an asteroid at the julian day in ET
+///
+/// iflag: the ephemeris, OptionalFlag::Heliocentric for the heliocentric
+/// distances, OptionalFlag::BarycentricPosition for the barycentric ones
+/// beyond Jupiter (geocentric otherwise, slower)
+pub fn orbit_max_min_true_distance(
+    tjd_et: f64,
+    ipl: Bodies,
+    iflag: CalcFlags,
+) -> Result<OrbitDistanceResult, SweError> {
+    let iflag = iflag.validate()?.bits();
+    // The Sun: distance of the Earth
+    let ipl = if ipl == Bodies::Sun {
+        Bodies::Sun as i32
+    } else {
+        ipl.orbit_ipl()?
+    };
+    let mut dmax = [0.0; 1];
+    let mut dmin = [0.0; 1];
+    let mut dtrue = [0.0; 1];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_orbit_max_min_true_distance(
+            tjd_et,
+            ipl,
+            iflag,
+            dmax.as_mut_ptr(),
+            dmin.as_mut_ptr(),
+            dtrue.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(
+        status,
+        &serr,
+        Call::Body {
+            ipl,
+            tjd: tjd_et,
+            iflag,
+        },
+    )?;
+    Ok(OrbitDistanceResult {
+        max_distance: dmax[0],
+        min_distance: dmin[0],
+        true_distance: dtrue[0],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swerust::handler_swe03;
+
+    const J2000: f64 = 2_451_545.0;
+
+    #[test]
+    fn orbital_elements_distance_heliocentric() {
+        let elements =
+            get_orbital_elements(J2000, Bodies::Mars, CalcFlags::new())
+                .unwrap();
+        let mars = handler_swe03::calc(
+            J2000,
+            Bodies::Mars,
+            OptionalFlag::Heliocentric.into(),
+        )
+        .unwrap();
+        assert!((elements.semimajor_axis - 1.5237).abs() < 1e-3);
+        // Apparent position (light time) for calc
+        assert!(
+            (elements.distance.true_distance - mars.distance_au).abs() < 1e-4
+        );
+        assert!(elements.distance.min_distance < mars.distance_au);
+        assert!(mars.distance_au < elements.distance.max_distance);
+    }
+
+    #[test]
+    fn orbital_elements_distance_moon_geocentric() {
+        let elements =
+            get_orbital_elements(J2000, Bodies::Moon, CalcFlags::new())
+                .unwrap();
+        let moon =
+            handler_swe03::calc(J2000, Bodies::Moon, CalcFlags::new()).unwrap();
+        assert!(
+            (elements.distance.true_distance - moon.distance_au).abs() < 1e-6
+        );
+    }
+
+    #[test]
+    fn orbital_elements_topocentric() {
+        // Only the ephemeris flag is used for the distance
+        let elements = get_orbital_elements(
+            J2000,
+            Bodies::Mars,
+            OptionalFlag::TopocentricPosition.into(),
+        )
+        .unwrap();
+        let distance = orbit_max_min_true_distance(
+            J2000,
+            Bodies::Mars,
+            OptionalFlag::Heliocentric.into(),
+        )
+        .unwrap();
+        assert_eq!(elements.distance, distance);
+    }
+}
@@ -1,6 +1,12 @@
 use crate::raw;
-use crate::sweconst::Bodies;
-use std::ffi::{CStr, CString};
+use crate::sweconst::{
+    Atmosphere, Bodies, BodyOrStar, CalcFlags, EclipseFlag, EclipseFlags,
+    EclipseType, GeoPosition, HeliacalEvent, HeliacalFlags, Observer,
+    RiseTransEvent, RiseTransFlag, RiseTransFlags,
+};
+use crate::sweerror::{self, Call, SweError};
+use std::os::raw::c_char;
+use std::ptr;
 
 /*
  * 7. Eclipses, risings, settings, meridian transits, planetary phenomena
@@ -12,31 +18,1195 @@ pub struct PhenoUtResult {
     pub elongation_of_planet: f64,
     pub apparent_dimaeter_of_disc: f64,
     pub apparent_magnitude: f64,
-    pub status: i32,
-    pub serr: String,
+    /// Some(SweError::MoshierFallback) if the ephemeris asked in iflag has
+    /// not been found
+    pub warning: Option<SweError>,
 }
 
-pub fn pheno_ut(tjd_ut: f64, ipl: Bodies, iflag: i32) -> PhenoUtResult {
+pub fn pheno_ut(
+    tjd_ut: f64,
+    ipl: Bodies,
+    iflag: CalcFlags,
+) -> Result<PhenoUtResult, SweError> {
+    let iflag = iflag.validate()?.bits();
+    let ipl = ipl.calc_ipl()?;
     let mut attr: [f64; 20] = [0.0; 20];
-    let mut serr = [0; 255];
-    let result = unsafe {
-        let p_attr = attr.as_mut_ptr();
-        let p_serr = serr.as_mut_ptr();
-        let status =
-            raw::swe_pheno_ut(tjd_ut, ipl as i32, iflag, p_attr, p_serr);
-        let s_serr = CString::from(CStr::from_ptr(p_serr))
-            .to_str()
-            .unwrap()
-            .to_string();
-        PhenoUtResult {
-            phase_angle: attr[0],
-            phase_illuminated: attr[1],
-            elongation_of_planet: attr[2],
-            apparent_dimaeter_of_disc: attr[3],
-            apparent_magnitude: attr[4],
-            serr: s_serr,
-            status,
-        }
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_pheno_ut(
+            tjd_ut,
+            ipl,
+            iflag,
+            attr.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(
+        status,
+        &serr,
+        Call::Body {
+            ipl,
+            tjd: tjd_ut,
+            iflag,
+        },
+    )?;
+    Ok(PhenoUtResult {
+        phase_angle: attr[0],
+        phase_illuminated: attr[1],
+        elongation_of_planet: attr[2],
+        apparent_dimaeter_of_disc: attr[3],
+        apparent_magnitude: attr[4],
+        warning: sweerror::moshier_fallback(iflag, status, &serr),
+    })
+}
+
+/*
+ * Solar eclipses
+ *
+ * All the times are julian days in UT, 0.0 in the c library when the
+ * contact doesn't exist (None here). iflag: only the ephemeris is used.
+ *
+ * The c library calls swe_set_topo with the place of the eclipse, set the
+ * observer again before a topocentric calculation (Ephemeris does it).
+ */
+
+/// Attributes of a solar eclipse at a place (attr)
+#[derive(Debug, Clone)]
+pub struct SolEclipseAttributes {
+    /// Fraction of solar diameter covered by moon, magnitude acc. to IMCCE
+    /// for the total and annular eclipses
+    pub magnitude: f64,
+    /// Ratio of lunar diameter to solar one
+    pub diameter_ratio: f64,
+    /// Fraction of solar disc covered by moon
+    pub obscuration: f64,
+    /// Diameter of core shadow in km
+    pub core_shadow_km: f64,
+    pub sun_azimuth: f64,
+    pub sun_true_altitude: f64,
+    pub sun_apparent_altitude: f64,
+    /// Elongation of moon in degrees
+    pub moon_elongation: f64,
+    /// Magnitude acc. to NASA
+    pub magnitude_nasa: f64,
+    pub saros_series: Option<i32>,
+    pub saros_member: Option<i32>,
+}
+
+/// Next solar eclipse on the earth (swe_sol_eclipse_when_glob)
+#[derive(Debug, Clone)]
+pub struct SolEclipseGlobResult {
+    pub flags: EclipseFlags,
+    pub eclipse_type: EclipseType,
+    /// Time of maximum eclipse
+    pub maximum: f64,
+    /// Time when eclipse takes place at local apparent noon
+    pub local_noon: Option<f64>,
+    pub begin: Option<f64>,
+    pub end: Option<f64>,
+    pub totality_begin: Option<f64>,
+    pub totality_end: Option<f64>,
+    pub center_line_begin: Option<f64>,
+    pub center_line_end: Option<f64>,
+    /// Place of the maximum eclipse
+    pub greatest: SolEclipseWhereResult,
+}
+
+/// Next solar eclipse visible at a place (swe_sol_eclipse_when_loc)
+#[derive(Debug, Clone)]
+pub struct SolEclipseLocResult {
+    /// Type and visibility (EclipseFlag::Visible, MaxVisible, ...)
+    pub flags: EclipseFlags,
+    pub eclipse_type: EclipseType,
+    /// Time of maximum eclipse
+    pub maximum: f64,
+    pub first_contact: Option<f64>,
+    pub second_contact: Option<f64>,
+    pub third_contact: Option<f64>,
+    pub fourth_contact: Option<f64>,
+    /// Sunrise between first and fourth contact
+    pub sunrise: Option<f64>,
+    /// Sunset between first and fourth contact
+    pub sunset: Option<f64>,
+    /// Attributes at the maximum
+    pub attributes: SolEclipseAttributes,
+}
+
+/// Solar eclipse at a place and time (swe_sol_eclipse_how)
+#[derive(Debug, Clone)]
+pub struct SolEclipseHowResult {
+    pub flags: EclipseFlags,
+    /// None if no eclipse is visible at the place
+    pub eclipse_type: Option<EclipseType>,
+    pub attributes: SolEclipseAttributes,
+}
+
+/// Central line of a solar eclipse (swe_sol_eclipse_where)
+#[derive(Debug, Clone)]
+pub struct SolEclipseWhereResult {
+    /// EclipseFlag::Central or NonCentral with the type
+    pub flags: EclipseFlags,
+    /// None if there is no solar eclipse at the time
+    pub eclipse_type: Option<EclipseType>,
+    /// Place where the eclipse is central, or maximal for the non central
+    /// eclipses (altitude 0.0)
+    pub position: GeoPosition,
+    pub attributes: SolEclipseAttributes,
+}
+
+/// Search the next (or previous) solar eclipse on the earth
+///
+/// ifltype: type wanted, EclipseFlags::new() for any type. With
+/// EclipseType::Total, Annular or AnnularTotal, add EclipseFlag::Central
+/// and/or NonCentral.
+pub fn sol_eclipse_when_glob(
+    tjd_start: f64,
+    iflag: CalcFlags,
+    ifltype: EclipseFlags,
+    backward: bool,
+) -> Result<SolEclipseGlobResult, SweError> {
+    let ifl = iflag.validate()?.bits();
+    let mut tret = [0.0; 10];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_sol_eclipse_when_glob(
+            tjd_start,
+            ifl,
+            ifltype.bits(),
+            tret.as_mut_ptr(),
+            backward as i32,
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, eclipse_call(tjd_start, ifl))?;
+    let flags = EclipseFlags::from_bits(status);
+    Ok(SolEclipseGlobResult {
+        flags,
+        eclipse_type: eclipse_found(flags)?,
+        maximum: tret[0],
+        local_noon: contact(tret[1]),
+        begin: contact(tret[2]),
+        end: contact(tret[3]),
+        totality_begin: contact(tret[4]),
+        totality_end: contact(tret[5]),
+        center_line_begin: contact(tret[6]),
+        center_line_end: contact(tret[7]),
+        greatest: sol_eclipse_where(tret[0], iflag)?,
+    })
+}
+
+/// Search the next (or previous) solar eclipse visible at a place
+pub fn sol_eclipse_when_loc(
+    tjd_start: f64,
+    iflag: CalcFlags,
+    geo: GeoPosition,
+    backward: bool,
+) -> Result<SolEclipseLocResult, SweError> {
+    let ifl = iflag.validate()?.bits();
+    let mut geopos = geo.geopos();
+    let mut tret = [0.0; 10];
+    let mut attr = [0.0; 20];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_sol_eclipse_when_loc(
+            tjd_start,
+            ifl,
+            geopos.as_mut_ptr(),
+            tret.as_mut_ptr(),
+            attr.as_mut_ptr(),
+            backward as i32,
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, eclipse_call(tjd_start, ifl))?;
+    let flags = EclipseFlags::from_bits(status);
+    Ok(SolEclipseLocResult {
+        flags,
+        eclipse_type: eclipse_found(flags)?,
+        maximum: tret[0],
+        first_contact: contact(tret[1]),
+        second_contact: contact(tret[2]),
+        third_contact: contact(tret[3]),
+        fourth_contact: contact(tret[4]),
+        sunrise: contact(tret[5]),
+        sunset: contact(tret[6]),
+        attributes: sol_eclipse_attributes(&attr),
+    })
+}
+
+/// Attributes of a solar eclipse at a place and time
+pub fn sol_eclipse_how(
+    tjd_ut: f64,
+    iflag: CalcFlags,
+    geo: GeoPosition,
+) -> Result<SolEclipseHowResult, SweError> {
+    let ifl = iflag.validate()?.bits();
+    let mut geopos = geo.geopos();
+    let mut attr = [0.0; 20];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_sol_eclipse_how(
+            tjd_ut,
+            ifl,
+            geopos.as_mut_ptr(),
+            attr.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, eclipse_call(tjd_ut, ifl))?;
+    let flags = EclipseFlags::from_bits(status);
+    Ok(SolEclipseHowResult {
+        flags,
+        eclipse_type: flags.eclipse_type(),
+        attributes: sol_eclipse_attributes(&attr),
+    })
+}
+
+/// Place of the central line (or of the maximum) of a solar eclipse at a
+/// time
+pub fn sol_eclipse_where(
+    tjd_ut: f64,
+    iflag: CalcFlags,
+) -> Result<SolEclipseWhereResult, SweError> {
+    let ifl = iflag.validate()?.bits();
+    // The c library write more than the 2 doubles documented
+    let mut geopos = [0.0; 10];
+    let mut attr = [0.0; 20];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_sol_eclipse_where(
+            tjd_ut,
+            ifl,
+            geopos.as_mut_ptr(),
+            attr.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
     };
-    result
+    sweerror::check_status(status, &serr, eclipse_call(tjd_ut, ifl))?;
+    let flags = EclipseFlags::from_bits(status);
+    Ok(SolEclipseWhereResult {
+        flags,
+        eclipse_type: flags.eclipse_type(),
+        position: GeoPosition::new(geopos[0], geopos[1], 0.0),
+        attributes: sol_eclipse_attributes(&attr),
+    })
+}
+
+/*
+ * Occultations by the moon
+ *
+ * Same conventions as the solar eclipses, the attributes are the ones of
+ * the occulted body instead of the sun (no saros). A star is searched like
+ * with handler_swe05::fixstar2_ut.
+ *
+ * The search is done until an occultation is found, which may never happen
+ * for a body with a high ecliptic latitude. With one_try, only the next
+ * conjunction of the moon is tested: eclipse_type is None if there is no
+ * occultation and the maximum is the time of the conjunction.
+ */
+
+/// SE_ECL_ONE_TRY, added to backward
+const ECL_ONE_TRY: i32 = 32 * 1024;
+
+/// Next occultation on the earth (swe_lun_occult_when_glob)
+#[derive(Debug, Clone)]
+pub struct OccultGlobResult {
+    pub flags: EclipseFlags,
+    /// None if no occultation (one_try)
+    pub eclipse_type: Option<EclipseType>,
+    /// Time of maximum occultation (or of the conjunction)
+    pub maximum: f64,
+    /// Time when occultation takes place at local apparent noon
+    pub local_noon: Option<f64>,
+    pub begin: Option<f64>,
+    pub end: Option<f64>,
+    pub totality_begin: Option<f64>,
+    pub totality_end: Option<f64>,
+    pub center_line_begin: Option<f64>,
+    pub center_line_end: Option<f64>,
+    /// Place of the maximum occultation, None if no occultation
+    pub greatest: Option<OccultWhereResult>,
+}
+
+/// Next occultation visible at a place (swe_lun_occult_when_loc)
+#[derive(Debug, Clone)]
+pub struct OccultLocResult {
+    /// Type and visibility (EclipseFlag::Visible, MaxVisible, ...)
+    pub flags: EclipseFlags,
+    /// None if no occultation (one_try)
+    pub eclipse_type: Option<EclipseType>,
+    /// Time of maximum occultation (or of the conjunction)
+    pub maximum: f64,
+    pub first_contact: Option<f64>,
+    pub second_contact: Option<f64>,
+    pub third_contact: Option<f64>,
+    pub fourth_contact: Option<f64>,
+    /// The occultation begins in daylight (SE_ECL_OCC_BEG_DAYLIGHT)
+    pub begin_daylight: bool,
+    /// The occultation ends in daylight (SE_ECL_OCC_END_DAYLIGHT)
+    pub end_daylight: bool,
+    /// Attributes at the maximum
+    pub attributes: SolEclipseAttributes,
+}
+
+/// Place of an occultation at a time (swe_lun_occult_where)
+#[derive(Debug, Clone)]
+pub struct OccultWhereResult {
+    /// EclipseFlag::Central or NonCentral with the type
+    pub flags: EclipseFlags,
+    /// None if there is no occultation at the time
+    pub eclipse_type: Option<EclipseType>,
+    /// Place where the occultation is central, or maximal for the non
+    /// central occultations (altitude 0.0)
+    pub position: GeoPosition,
+    pub attributes: SolEclipseAttributes,
+}
+
+/// Search the next (or previous) occultation of a planet or a star by the
+/// moon on the earth
+///
+/// ifltype: same as sol_eclipse_when_glob
+pub fn lun_occult_when_glob(
+    tjd_start: f64,
+    occulted: &BodyOrStar,
+    iflag: CalcFlags,
+    ifltype: EclipseFlags,
+    backward: bool,
+    one_try: bool,
+) -> Result<OccultGlobResult, SweError> {
+    let ifl = iflag.validate()?.bits();
+    let ipl = occulted.ipl()?;
+    let mut star = occulted.star_buf()?;
+    let mut tret = [0.0; 10];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_lun_occult_when_glob(
+            tjd_start,
+            ipl,
+            star.as_mut().map_or(ptr::null_mut(), |s| s.as_mut_ptr()),
+            ifl,
+            ifltype.bits(),
+            tret.as_mut_ptr(),
+            occult_backward(backward, one_try),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, occulted.call(tjd_start, ifl))?;
+    let flags = EclipseFlags::from_bits(status);
+    let eclipse_type = flags.eclipse_type();
+    let greatest = match eclipse_type {
+        Some(_) => Some(lun_occult_where(tret[0], occulted, iflag)?),
+        None => None,
+    };
+    Ok(OccultGlobResult {
+        flags,
+        eclipse_type,
+        maximum: tret[0],
+        local_noon: contact(tret[1]),
+        begin: contact(tret[2]),
+        end: contact(tret[3]),
+        totality_begin: contact(tret[4]),
+        totality_end: contact(tret[5]),
+        center_line_begin: contact(tret[6]),
+        center_line_end: contact(tret[7]),
+        greatest,
+    })
+}
+
+/// Search the next (or previous) occultation of a planet or a star by the
+/// moon visible at a place
+pub fn lun_occult_when_loc(
+    tjd_start: f64,
+    occulted: &BodyOrStar,
+    iflag: CalcFlags,
+    geo: GeoPosition,
+    backward: bool,
+    one_try: bool,
+) -> Result<OccultLocResult, SweError> {
+    let ifl = iflag.validate()?.bits();
+    let ipl = occulted.ipl()?;
+    let mut star = occulted.star_buf()?;
+    let mut geopos = geo.geopos();
+    let mut tret = [0.0; 10];
+    let mut attr = [0.0; 20];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_lun_occult_when_loc(
+            tjd_start,
+            ipl,
+            star.as_mut().map_or(ptr::null_mut(), |s| s.as_mut_ptr()),
+            ifl,
+            geopos.as_mut_ptr(),
+            tret.as_mut_ptr(),
+            attr.as_mut_ptr(),
+            occult_backward(backward, one_try),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, occulted.call(tjd_start, ifl))?;
+    let flags = EclipseFlags::from_bits(status);
+    Ok(OccultLocResult {
+        flags,
+        eclipse_type: flags.eclipse_type(),
+        maximum: tret[0],
+        first_contact: contact(tret[1]),
+        second_contact: contact(tret[2]),
+        third_contact: contact(tret[3]),
+        fourth_contact: contact(tret[4]),
+        // Same bits as the penumbral phase of the lunar eclipses
+        begin_daylight: flags.contains(EclipseFlag::PenumbralBeginVisible),
+        end_daylight: flags.contains(EclipseFlag::PenumbralEndVisible),
+        attributes: sol_eclipse_attributes(&attr),
+    })
+}
+
+/// Place of the central line (or of the maximum) of an occultation at a
+/// time
+pub fn lun_occult_where(
+    tjd_ut: f64,
+    occulted: &BodyOrStar,
+    iflag: CalcFlags,
+) -> Result<OccultWhereResult, SweError> {
+    let ifl = iflag.validate()?.bits();
+    let ipl = occulted.ipl()?;
+    let mut star = occulted.star_buf()?;
+    // The c library write more than the 2 doubles documented
+    let mut geopos = [0.0; 10];
+    let mut attr = [0.0; 20];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_lun_occult_where(
+            tjd_ut,
+            ipl,
+            star.as_mut().map_or(ptr::null_mut(), |s| s.as_mut_ptr()),
+            ifl,
+            geopos.as_mut_ptr(),
+            attr.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, occulted.call(tjd_ut, ifl))?;
+    let flags = EclipseFlags::from_bits(status);
+    Ok(OccultWhereResult {
+        flags,
+        eclipse_type: flags.eclipse_type(),
+        position: GeoPosition::new(geopos[0], geopos[1], 0.0),
+        attributes: sol_eclipse_attributes(&attr),
+    })
+}
+
+fn occult_backward(backward: bool, one_try: bool) -> i32 {
+    if one_try {
+        backward as i32 | ECL_ONE_TRY
+    } else {
+        backward as i32
+    }
+}
+
+/*
+ * Risings, settings and meridian transits
+ *
+ * atpress: atmospheric pressure in mbar/hPa, 0.0 to estimate it from the
+ * altitude of the observer. attemp: temperature in degrees Celsius.
+ */
+
+/// Returned by swe_rise_trans when the body is circumpolar
+const RISE_TRANS_CIRCUMPOLAR: i32 = -2;
+
+/// Time of a rising, setting or transit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiseTransResult {
+    pub event: RiseTransEvent,
+    /// Julian day in UT
+    pub tjd_ut: f64,
+}
+
+/// Next rising, setting or transit of a planet or a star after tjd_ut
+///
+/// Ok(None) if the body doesn't rise or set (circumpolar)
+pub fn rise_trans(
+    tjd_ut: f64,
+    body: &BodyOrStar,
+    iflag: CalcFlags,
+    rsmi: RiseTransFlags,
+    geo: GeoPosition,
+    atpress: f64,
+    attemp: f64,
+) -> Result<Option<RiseTransResult>, SweError> {
+    let epheflag = iflag.validate()?.bits();
+    let ipl = body.ipl()?;
+    let mut star = body.star_buf()?;
+    let mut geopos = geo.geopos();
+    let mut tret = [0.0; 10];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_rise_trans(
+            tjd_ut,
+            ipl,
+            star.as_mut().map_or(ptr::null_mut(), |s| s.as_mut_ptr()),
+            epheflag,
+            rsmi.bits(),
+            geopos.as_mut_ptr(),
+            atpress,
+            attemp,
+            tret.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    rise_trans_result(status, &serr, body.call(tjd_ut, epheflag), rsmi, tret[0])
+}
+
+/// Same as rise_trans, with a local horizon at horhgt degrees where the
+/// body rises or sets
+#[allow(clippy::too_many_arguments)]
+pub fn rise_trans_true_hor(
+    tjd_ut: f64,
+    body: &BodyOrStar,
+    iflag: CalcFlags,
+    rsmi: RiseTransFlags,
+    geo: GeoPosition,
+    atpress: f64,
+    attemp: f64,
+    horhgt: f64,
+) -> Result<Option<RiseTransResult>, SweError> {
+    let epheflag = iflag.validate()?.bits();
+    let ipl = body.ipl()?;
+    let mut star = body.star_buf()?;
+    let mut geopos = geo.geopos();
+    let mut tret = [0.0; 10];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_rise_trans_true_hor(
+            tjd_ut,
+            ipl,
+            star.as_mut().map_or(ptr::null_mut(), |s| s.as_mut_ptr()),
+            epheflag,
+            rsmi.bits(),
+            geopos.as_mut_ptr(),
+            atpress,
+            attemp,
+            horhgt,
+            tret.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    rise_trans_result(status, &serr, body.call(tjd_ut, epheflag), rsmi, tret[0])
+}
+
+/// Twilights, sunrise, solar noon and sunset of a day, in chronological
+/// order (julian days in UT)
+///
+/// None when the event doesn't happen during the day (polar day or night,
+/// twilight all the night in summer at high latitude)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TwilightResult {
+    /// Sun at -18 degrees, rising
+    pub astronomical_dawn: Option<f64>,
+    /// Sun at -12 degrees, rising
+    pub nautical_dawn: Option<f64>,
+    /// Sun at -6 degrees, rising
+    pub civil_dawn: Option<f64>,
+    pub sunrise: Option<f64>,
+    /// Upper meridian transit of the sun
+    pub solar_noon: Option<f64>,
+    pub sunset: Option<f64>,
+    pub civil_dusk: Option<f64>,
+    pub nautical_dusk: Option<f64>,
+    pub astronomical_dusk: Option<f64>,
+}
+
+/// Twilights, sunrise, solar noon and sunset during the 24 hours after
+/// tjd_ut (the beginning of the day at the place, in UT)
+pub fn twilight(
+    tjd_ut: f64,
+    iflag: CalcFlags,
+    geo: GeoPosition,
+    atpress: f64,
+    attemp: f64,
+) -> Result<TwilightResult, SweError> {
+    let sun = BodyOrStar::Body(Bodies::Sun);
+    let event = |rsmi: RiseTransFlags| -> Result<Option<f64>, SweError> {
+        let result =
+            rise_trans(tjd_ut, &sun, iflag, rsmi, geo, atpress, attemp)?;
+        // The next event is another day
+        Ok(result.map(|r| r.tjd_ut).filter(|t| *t < tjd_ut + 1.0))
+    };
+    use RiseTransEvent::{Rise, Set, UpperTransit};
+    use RiseTransFlag::{AstroTwilight, CivilTwilight, NauticTwilight};
+    Ok(TwilightResult {
+        astronomical_dawn: event(Rise | AstroTwilight)?,
+        nautical_dawn: event(Rise | NauticTwilight)?,
+        civil_dawn: event(Rise | CivilTwilight)?,
+        sunrise: event(Rise.into())?,
+        solar_noon: event(UpperTransit.into())?,
+        sunset: event(Set.into())?,
+        civil_dusk: event(Set | CivilTwilight)?,
+        nautical_dusk: event(Set | NauticTwilight)?,
+        astronomical_dusk: event(Set | AstroTwilight)?,
+    })
+}
+
+fn rise_trans_result(
+    status: i32,
+    serr: &[c_char],
+    call: Call,
+    rsmi: RiseTransFlags,
+    tjd_ut: f64,
+) -> Result<Option<RiseTransResult>, SweError> {
+    if status == RISE_TRANS_CIRCUMPOLAR {
+        return Ok(None);
+    }
+    sweerror::check_status(status, serr, call)?;
+    Ok(Some(RiseTransResult {
+        event: rsmi.event(),
+        tjd_ut,
+    }))
+}
+
+/*
+ * Heliacal events
+ *
+ * Between the latitudes 60s and 60n, for the Moon, the planets from
+ * Mercury to Neptune and the fixed stars. helflag is the HeliacalFlags
+ * with the ephemeris flag of iflag, the other flags of iflag are ignored.
+ */
+
+/// Returned by swe_vis_limit_mag when the object is below the horizon
+const VIS_LIMIT_BELOW_HORIZON: i32 = -2;
+
+/// helflag of the c library: the ephemeris of iflag with the HeliacalFlags
+fn heliacal_flags(
+    iflag: CalcFlags,
+    helflag: HeliacalFlags,
+) -> Result<i32, SweError> {
+    Ok(iflag.ephemeris().validate()?.bits() | helflag.bits())
+}
+
+/// Next heliacal event (swe_heliacal_ut)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeliacalResult {
+    pub event: HeliacalEvent,
+    /// Start of visibility, julian day in UT
+    pub start_visibility: f64,
+    /// Optimum visibility (None with HeliacalFlag::NoDetails)
+    pub optimum_visibility: Option<f64>,
+    /// End of visibility (None with HeliacalFlag::NoDetails)
+    pub end_visibility: Option<f64>,
+}
+
+/// Details of a heliacal event at a time (swe_heliacal_pheno_ut)
+///
+/// Angles in degrees, times in julian days UT. VR: criterion of
+/// Schaefer-Vreman, Yallop: criterion of Yallop for the crescent of the
+/// Moon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeliacalPhenoResult {
+    /// Topocentric altitude of the object (unrefracted)
+    pub altitude: f64,
+    /// Apparent altitude of the object (refracted)
+    pub apparent_altitude: f64,
+    /// Geocentric altitude of the object
+    pub geocentric_altitude: f64,
+    pub azimuth: f64,
+    /// Topocentric altitude of the sun
+    pub sun_altitude: f64,
+    pub sun_azimuth: f64,
+    /// Actual topocentric arcus visionis
+    pub topocentric_arcus_visionis: f64,
+    /// Actual geocentric arcus visionis
+    pub arcus_visionis: f64,
+    /// Actual difference between the azimuths of the object and the sun
+    pub azimuth_difference: f64,
+    /// Actual difference between the longitudes of the object and the sun
+    pub longitude_difference: f64,
+    /// Extinction coefficient
+    pub extinction: f64,
+    /// Smallest topocentric arcus visionis
+    pub min_topocentric_arcus_visionis: f64,
+    /// First time the object is visible (VR)
+    pub first_visible: f64,
+    /// Optimum time the object is visible (VR)
+    pub best_visible: f64,
+    /// Last time the object is visible (VR)
+    pub last_visible: f64,
+    /// Best time the object is visible (Yallop)
+    pub best_visible_yallop: f64,
+    /// Crescent width of the moon
+    pub moon_crescent_width: f64,
+    /// q-test value of Yallop
+    pub q_yallop: f64,
+    /// q-test criterion of Yallop
+    pub q_criterion: f64,
+    /// Parallax of the object
+    pub parallax: f64,
+    /// Magnitude of the object
+    pub magnitude: f64,
+    /// Rise or set time of the object
+    pub rise_set: f64,
+    /// Rise or set time of the sun
+    pub sun_rise_set: f64,
+    /// Rise or set time of the object minus the one of the sun
+    pub lag: f64,
+    /// Visibility duration
+    pub visibility_duration: f64,
+    /// Crescent length of the moon
+    pub moon_crescent_length: f64,
+    /// Illumination in %
+    pub illumination: f64,
+}
+
+/// Limiting visual magnitude (swe_vis_limit_mag), the object is visible if
+/// its magnitude is lower
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VisLimitMagResult {
+    pub limiting_magnitude: f64,
+    pub altitude: f64,
+    pub azimuth: f64,
+    pub sun_altitude: f64,
+    pub sun_azimuth: f64,
+    pub moon_altitude: f64,
+    pub moon_azimuth: f64,
+    /// Magnitude of the object
+    pub magnitude: f64,
+    /// Scotopic (night) vision, photopic (day) vision otherwise
+    pub scotopic: bool,
+    /// Near the limit between photopic and scotopic vision
+    pub near_limit: bool,
+}
+
+impl VisLimitMagResult {
+    /// The object is visible
+    pub fn is_visible(&self) -> bool {
+        self.magnitude < self.limiting_magnitude
+    }
+}
+
+/// Search the next heliacal event of a planet or a star after tjd_start
+/// (UT)
+#[allow(clippy::too_many_arguments)]
+pub fn heliacal_ut(
+    tjd_start: f64,
+    geo: GeoPosition,
+    atm: Atmosphere,
+    obs: Observer,
+    object: &BodyOrStar,
+    event: HeliacalEvent,
+    iflag: CalcFlags,
+    helflag: HeliacalFlags,
+) -> Result<HeliacalResult, SweError> {
+    let helflag = heliacal_flags(iflag, helflag)?;
+    let mut name = object.object_name()?;
+    let mut dgeo = geo.geopos();
+    let mut datm = atm.datm();
+    let mut dobs = obs.dobs();
+    let mut dret = [0.0; 50];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_heliacal_ut(
+            tjd_start,
+            dgeo.as_mut_ptr(),
+            datm.as_mut_ptr(),
+            dobs.as_mut_ptr(),
+            name.as_mut_ptr(),
+            event as i32,
+            helflag,
+            dret.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, object.call(tjd_start, helflag))?;
+    Ok(HeliacalResult {
+        event,
+        start_visibility: dret[0],
+        optimum_visibility: contact(dret[1]),
+        end_visibility: contact(dret[2]),
+    })
+}
+
+/// Details of a heliacal event of a planet or a star at a time
+#[allow(clippy::too_many_arguments)]
+pub fn heliacal_pheno_ut(
+    tjd_ut: f64,
+    geo: GeoPosition,
+    atm: Atmosphere,
+    obs: Observer,
+    object: &BodyOrStar,
+    event: HeliacalEvent,
+    iflag: CalcFlags,
+    helflag: HeliacalFlags,
+) -> Result<HeliacalPhenoResult, SweError> {
+    let helflag = heliacal_flags(iflag, helflag)?;
+    let mut name = object.object_name()?;
+    let mut dgeo = geo.geopos();
+    let mut datm = atm.datm();
+    let mut dobs = obs.dobs();
+    let mut darr = [0.0; 50];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_heliacal_pheno_ut(
+            tjd_ut,
+            dgeo.as_mut_ptr(),
+            datm.as_mut_ptr(),
+            dobs.as_mut_ptr(),
+            name.as_mut_ptr(),
+            event as i32,
+            helflag,
+            darr.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, object.call(tjd_ut, helflag))?;
+    Ok(HeliacalPhenoResult {
+        altitude: darr[0],
+        apparent_altitude: darr[1],
+        geocentric_altitude: darr[2],
+        azimuth: darr[3],
+        sun_altitude: darr[4],
+        sun_azimuth: darr[5],
+        topocentric_arcus_visionis: darr[6],
+        arcus_visionis: darr[7],
+        azimuth_difference: darr[8],
+        longitude_difference: darr[9],
+        extinction: darr[10],
+        min_topocentric_arcus_visionis: darr[11],
+        first_visible: darr[12],
+        best_visible: darr[13],
+        last_visible: darr[14],
+        best_visible_yallop: darr[15],
+        moon_crescent_width: darr[16],
+        q_yallop: darr[17],
+        q_criterion: darr[18],
+        parallax: darr[19],
+        magnitude: darr[20],
+        rise_set: darr[21],
+        sun_rise_set: darr[22],
+        lag: darr[23],
+        visibility_duration: darr[24],
+        moon_crescent_length: darr[25],
+        illumination: darr[27],
+    })
+}
+
+/// Limiting visual magnitude for a planet or a star at a time (not for the
+/// Sun)
+///
+/// Ok(None) if the object is below the horizon
+pub fn vis_limit_mag(
+    tjd_ut: f64,
+    geo: GeoPosition,
+    atm: Atmosphere,
+    obs: Observer,
+    object: &BodyOrStar,
+    iflag: CalcFlags,
+    helflag: HeliacalFlags,
+) -> Result<Option<VisLimitMagResult>, SweError> {
+    let helflag = heliacal_flags(iflag, helflag)?;
+    let mut name = object.object_name()?;
+    let mut dgeo = geo.geopos();
+    let mut datm = atm.datm();
+    let mut dobs = obs.dobs();
+    let mut dret = [0.0; 50];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_vis_limit_mag(
+            tjd_ut,
+            dgeo.as_mut_ptr(),
+            datm.as_mut_ptr(),
+            dobs.as_mut_ptr(),
+            name.as_mut_ptr(),
+            helflag,
+            dret.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    if status == VIS_LIMIT_BELOW_HORIZON {
+        return Ok(None);
+    }
+    sweerror::check_status(status, &serr, object.call(tjd_ut, helflag))?;
+    Ok(Some(VisLimitMagResult {
+        limiting_magnitude: dret[0],
+        altitude: dret[1],
+        azimuth: dret[2],
+        sun_altitude: dret[3],
+        sun_azimuth: dret[4],
+        moon_altitude: dret[5],
+        moon_azimuth: dret[6],
+        magnitude: dret[7],
+        scotopic: status & 1 != 0,
+        near_limit: status & 2 != 0,
+    }))
+}
+
+fn sol_eclipse_attributes(attr: &[f64; 20]) -> SolEclipseAttributes {
+    SolEclipseAttributes {
+        magnitude: attr[0],
+        diameter_ratio: attr[1],
+        obscuration: attr[2],
+        core_shadow_km: attr[3],
+        sun_azimuth: attr[4],
+        sun_true_altitude: attr[5],
+        sun_apparent_altitude: attr[6],
+        moon_elongation: attr[7],
+        magnitude_nasa: attr[8],
+        saros_series: saros(attr[9]),
+        saros_member: saros(attr[10]),
+    }
+}
+
+/// The search functions always return an eclipse
+fn eclipse_found(flags: EclipseFlags) -> Result<EclipseType, SweError> {
+    flags.eclipse_type().ok_or_else(|| {
+        SweError::Calculation(format!(
+            "no eclipse type in the flags {}",
+            flags.bits()
+        ))
+    })
+}
+
+/// 0.0 -> None
+/// Call of the eclipse functions, to classify their errors: the positions
+/// of the Moon and the Sun
+fn eclipse_call(tjd: f64, ifl: i32) -> Call {
+    Call::Body {
+        ipl: Bodies::Moon as i32,
+        tjd,
+        iflag: ifl,
+    }
+}
+
+fn contact(tjd: f64) -> Option<f64> {
+    if tjd == 0.0 {
+        None
+    } else {
+        Some(tjd)
+    }
+}
+
+/// -99999999 -> None
+fn saros(value: f64) -> Option<i32> {
+    if value == -99999999.0 {
+        None
+    } else {
+        Some(value as i32)
+    }
+}
+
+/*
+ * Lunar eclipses
+ *
+ * Same conventions as the solar eclipses
+ */
+
+/// Attributes of a lunar eclipse (attr)
+#[derive(Debug, Clone)]
+pub struct LunEclipseAttributes {
+    pub umbral_magnitude: f64,
+    pub penumbral_magnitude: f64,
+    /// Azimuth and altitudes of the moon, 0.0 without observer
+    pub moon_azimuth: f64,
+    pub moon_true_altitude: f64,
+    pub moon_apparent_altitude: f64,
+    /// Distance of moon from opposition in degrees
+    pub opposition_distance: f64,
+    pub saros_series: Option<i32>,
+    pub saros_member: Option<i32>,
+}
+
+/// Next lunar eclipse (swe_lun_eclipse_when)
+#[derive(Debug, Clone)]
+pub struct LunEclipseResult {
+    pub flags: EclipseFlags,
+    pub eclipse_type: EclipseType,
+    /// Time of maximum eclipse
+    pub maximum: f64,
+    pub partial_begin: Option<f64>,
+    pub partial_end: Option<f64>,
+    pub totality_begin: Option<f64>,
+    pub totality_end: Option<f64>,
+    pub penumbral_begin: Option<f64>,
+    pub penumbral_end: Option<f64>,
+    /// Attributes at the maximum
+    pub attributes: LunEclipseAttributes,
+}
+
+/// Next lunar eclipse visible at a place (swe_lun_eclipse_when_loc)
+#[derive(Debug, Clone)]
+pub struct LunEclipseLocResult {
+    /// Type and visibility (EclipseFlag::Visible, MaxVisible,
+    /// FirstVisible for the partial phase begin, ...)
+    pub flags: EclipseFlags,
+    pub eclipse_type: EclipseType,
+    /// Time of maximum eclipse
+    pub maximum: f64,
+    pub partial_begin: Option<f64>,
+    pub partial_end: Option<f64>,
+    pub totality_begin: Option<f64>,
+    pub totality_end: Option<f64>,
+    pub penumbral_begin: Option<f64>,
+    pub penumbral_end: Option<f64>,
+    /// Moonrise during the eclipse
+    pub moonrise: Option<f64>,
+    /// Moonset during the eclipse
+    pub moonset: Option<f64>,
+    /// Attributes at the maximum
+    pub attributes: LunEclipseAttributes,
+}
+
+/// Lunar eclipse at a time (swe_lun_eclipse_how)
+#[derive(Debug, Clone)]
+pub struct LunEclipseHowResult {
+    pub flags: EclipseFlags,
+    /// None if there is no eclipse
+    pub eclipse_type: Option<EclipseType>,
+    pub attributes: LunEclipseAttributes,
+}
+
+/// Search the next (or previous) lunar eclipse
+///
+/// ifltype: EclipseType::Total, Partial, Penumbral or EclipseFlags::new()
+/// for any type
+pub fn lun_eclipse_when(
+    tjd_start: f64,
+    iflag: CalcFlags,
+    ifltype: EclipseFlags,
+    backward: bool,
+) -> Result<LunEclipseResult, SweError> {
+    let ifl = iflag.validate()?.bits();
+    let mut tret = [0.0; 10];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_lun_eclipse_when(
+            tjd_start,
+            ifl,
+            ifltype.bits(),
+            tret.as_mut_ptr(),
+            backward as i32,
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, eclipse_call(tjd_start, ifl))?;
+    let flags = EclipseFlags::from_bits(status);
+    Ok(LunEclipseResult {
+        flags,
+        eclipse_type: eclipse_found(flags)?,
+        maximum: tret[0],
+        partial_begin: contact(tret[2]),
+        partial_end: contact(tret[3]),
+        totality_begin: contact(tret[4]),
+        totality_end: contact(tret[5]),
+        penumbral_begin: contact(tret[6]),
+        penumbral_end: contact(tret[7]),
+        attributes: lun_eclipse_how(tret[0], iflag, None)?.attributes,
+    })
+}
+
+/// Search the next (or previous) lunar eclipse visible at a place
+pub fn lun_eclipse_when_loc(
+    tjd_start: f64,
+    iflag: CalcFlags,
+    geo: GeoPosition,
+    backward: bool,
+) -> Result<LunEclipseLocResult, SweError> {
+    let ifl = iflag.validate()?.bits();
+    let mut geopos = geo.geopos();
+    let mut tret = [0.0; 10];
+    let mut attr = [0.0; 20];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_lun_eclipse_when_loc(
+            tjd_start,
+            ifl,
+            geopos.as_mut_ptr(),
+            tret.as_mut_ptr(),
+            attr.as_mut_ptr(),
+            backward as i32,
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, eclipse_call(tjd_start, ifl))?;
+    let flags = EclipseFlags::from_bits(status);
+    Ok(LunEclipseLocResult {
+        flags,
+        eclipse_type: eclipse_found(flags)?,
+        maximum: tret[0],
+        partial_begin: contact(tret[2]),
+        partial_end: contact(tret[3]),
+        totality_begin: contact(tret[4]),
+        totality_end: contact(tret[5]),
+        penumbral_begin: contact(tret[6]),
+        penumbral_end: contact(tret[7]),
+        moonrise: contact(tret[8]),
+        moonset: contact(tret[9]),
+        attributes: lun_eclipse_attributes(&attr),
+    })
+}
+
+/// Attributes of a lunar eclipse at a time, the position of the moon in
+/// the sky is calculated only with an observer
+pub fn lun_eclipse_how(
+    tjd_ut: f64,
+    iflag: CalcFlags,
+    geo: Option<GeoPosition>,
+) -> Result<LunEclipseHowResult, SweError> {
+    let ifl = iflag.validate()?.bits();
+    let mut geopos = geo.map(GeoPosition::geopos);
+    let mut attr = [0.0; 20];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_lun_eclipse_how(
+            tjd_ut,
+            ifl,
+            geopos.as_mut().map_or(ptr::null_mut(), |g| g.as_mut_ptr()),
+            attr.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, eclipse_call(tjd_ut, ifl))?;
+    let flags = EclipseFlags::from_bits(status);
+    Ok(LunEclipseHowResult {
+        flags,
+        eclipse_type: flags.eclipse_type(),
+        attributes: lun_eclipse_attributes(&attr),
+    })
+}
+
+/// All the lunar eclipses between two julian days in UT, in chronological
+/// order if tjd_start < tjd_end, in reverse order otherwise
+pub fn lun_eclipses(
+    tjd_start: f64,
+    tjd_end: f64,
+    iflag: CalcFlags,
+    ifltype: EclipseFlags,
+) -> Result<Vec<LunEclipseResult>, SweError> {
+    let backward = tjd_end < tjd_start;
+    let mut eclipses = Vec::new();
+    let mut tjd = tjd_start;
+    loop {
+        let eclipse = lun_eclipse_when(tjd, iflag, ifltype, backward)?;
+        if (backward && eclipse.maximum < tjd_end)
+            || (!backward && eclipse.maximum > tjd_end)
+        {
+            return Ok(eclipses);
+        }
+        // Two lunar eclipses are separated by at least a lunar month
+        tjd = if backward {
+            eclipse.maximum - 1.0
+        } else {
+            eclipse.maximum + 1.0
+        };
+        eclipses.push(eclipse);
+    }
+}
+
+fn lun_eclipse_attributes(attr: &[f64; 20]) -> LunEclipseAttributes {
+    LunEclipseAttributes {
+        umbral_magnitude: attr[0],
+        penumbral_magnitude: attr[1],
+        moon_azimuth: attr[4],
+        moon_true_altitude: attr[5],
+        moon_apparent_altitude: attr[6],
+        opposition_distance: attr[7],
+        saros_series: saros(attr[9]),
+        saros_member: saros(attr[10]),
+    }
 }
@@ -0,0 +1,215 @@
+use crate::raw;
+use crate::sweconst::{
+    Bodies, CalcFlags, CoordinateSystem, GeoPosition, OptionalFlag, Refraction,
+};
+use crate::sweerror::SweError;
+use crate::swerust::handler_swe03::{self, CalcUtResult};
+
+/*
+ * 7. Horizontal coordinates (azimuth, altitude) and refraction
+ *
+ * The azimuth is measured from the south point to the west. atpress:
+ * atmospheric pressure in mbar/hPa, 0.0 to estimate it from the altitude of
+ * the observer. attemp: temperature in degrees Celsius.
+ */
+
+/// SE_LAPSE_RATE, default of swe_set_lapse_rate
+pub const LAPSE_RATE_DEFAULT: f64 = 0.0065;
+
+/// Position of a body in the sky of the observer (swe_azalt)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HorizonResult {
+    /// Azimuth from the south point to the west
+    pub azimuth: f64,
+    /// Altitude above the horizon without refraction
+    pub true_altitude: f64,
+    /// Altitude above the horizon with refraction
+    pub apparent_altitude: f64,
+}
+
+impl HorizonResult {
+    /// The center of the body is above the horizon (geometric, without
+    /// refraction)
+    pub fn is_above_horizon(&self) -> bool {
+        self.true_altitude > 0.0
+    }
+
+    /// The center of the body is visible above the horizon (with
+    /// refraction)
+    pub fn is_visible(&self) -> bool {
+        self.apparent_altitude > 0.0
+    }
+}
+
+/// Ecliptic or equatorial position from horizontal coordinates
+/// (swe_azalt_rev)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AzaltRevResult {
+    /// Longitude, or right ascension with CoordinateSystem::Equatorial
+    pub longitude: f64,
+    /// Latitude, or declination with CoordinateSystem::Equatorial
+    pub latitude: f64,
+}
+
+/// Altitudes of swe_refrac_extended (dret)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefracResult {
+    /// Converted altitude, or the input altitude if the body is below the
+    /// ideal horizon
+    pub altitude: f64,
+    pub true_altitude: f64,
+    pub apparent_altitude: f64,
+    pub refraction: f64,
+    /// Dip of the horizon
+    pub dip: f64,
+}
+
+/// Horizontal coordinates from ecliptic or equatorial coordinates
+/// (longitude/latitude or right ascension/declination in degrees)
+pub fn azalt(
+    tjd_ut: f64,
+    coord: CoordinateSystem,
+    geo: GeoPosition,
+    atpress: f64,
+    attemp: f64,
+    longitude: f64,
+    latitude: f64,
+) -> HorizonResult {
+    let mut geopos = geo.geopos();
+    let mut xin = [longitude, latitude, 1.0];
+    let mut xaz = [0.0; 3];
+    unsafe {
+        raw::swe_azalt(
+            tjd_ut,
+            coord as i32,
+            geopos.as_mut_ptr(),
+            atpress,
+            attemp,
+            xin.as_mut_ptr(),
+            xaz.as_mut_ptr(),
+        )
+    };
+    HorizonResult {
+        azimuth: xaz[0],
+        true_altitude: xaz[1],
+        apparent_altitude: xaz[2],
+    }
+}
+
+/// Horizontal coordinates of a position of handler_swe03::calc_ut, coord
+/// is CoordinateSystem::Equatorial if it has been calculated with
+/// OptionalFlag::EquatorialPosition
+pub fn azalt_calc(
+    tjd_ut: f64,
+    calc: &CalcUtResult,
+    coord: CoordinateSystem,
+    geo: GeoPosition,
+    atpress: f64,
+    attemp: f64,
+) -> HorizonResult {
+    azalt(
+        tjd_ut,
+        coord,
+        geo,
+        atpress,
+        attemp,
+        calc.longitude,
+        calc.latitude,
+    )
+}
+
+/// Ecliptic or equatorial coordinates from the azimuth and the true
+/// altitude (see refrac_extended for an apparent altitude)
+pub fn azalt_rev(
+    tjd_ut: f64,
+    coord: CoordinateSystem,
+    geo: GeoPosition,
+    azimuth: f64,
+    true_altitude: f64,
+) -> AzaltRevResult {
+    let mut geopos = geo.geopos();
+    let mut xin = [azimuth, true_altitude];
+    let mut xout = [0.0; 3];
+    unsafe {
+        raw::swe_azalt_rev(
+            tjd_ut,
+            coord as i32,
+            geopos.as_mut_ptr(),
+            xin.as_mut_ptr(),
+            xout.as_mut_ptr(),
+        )
+    };
+    AzaltRevResult {
+        longitude: xout[0],
+        latitude: xout[1],
+    }
+}
+
+/// True altitude from apparent altitude or the reverse, for an observer at
+/// geoalt meters (the horizon and the bodies may have a negative altitude)
+///
+/// lapse_rate: dT/dh in deg K/m, LAPSE_RATE_DEFAULT for the standard
+/// atmosphere
+pub fn refrac_extended(
+    inalt: f64,
+    geoalt: f64,
+    atpress: f64,
+    attemp: f64,
+    lapse_rate: f64,
+    calc_flag: Refraction,
+) -> RefracResult {
+    let mut dret = [0.0; 20];
+    let altitude = unsafe {
+        raw::swe_refrac_extended(
+            inalt,
+            geoalt,
+            atpress,
+            attemp,
+            lapse_rate,
+            calc_flag as i32,
+            dret.as_mut_ptr(),
+        )
+    };
+    RefracResult {
+        altitude,
+        true_altitude: dret[0],
+        apparent_altitude: dret[1],
+        refraction: dret[2],
+        dip: dret[3],
+    }
+}
+
+/// Set the lapse rate used by azalt for the apparent altitude, None for
+/// LAPSE_RATE_DEFAULT
+pub fn set_lapse_rate(lapse_rate: Option<f64>) {
+    unsafe { raw::swe_set_lapse_rate(lapse_rate.unwrap_or(LAPSE_RATE_DEFAULT)) }
+}
+
+/// Horizontal coordinates of a body
+///
+/// iflag: only the ephemeris and OptionalFlag::TopocentricPosition are used
+/// (the observer of swe_set_topo must be geo)
+pub fn body_azalt(
+    tjd_ut: f64,
+    ipl: Bodies,
+    iflag: CalcFlags,
+    geo: GeoPosition,
+    atpress: f64,
+    attemp: f64,
+) -> Result<HorizonResult, SweError> {
+    // Ecliptic of date, as expected by swe_azalt
+    let iflag = if iflag.contains(OptionalFlag::TopocentricPosition) {
+        iflag.ephemeris().with(OptionalFlag::TopocentricPosition)
+    } else {
+        iflag.ephemeris()
+    };
+    let calc = handler_swe03::calc_ut(tjd_ut, ipl, iflag)?;
+    Ok(azalt_calc(
+        tjd_ut,
+        &calc,
+        CoordinateSystem::Ecliptic,
+        geo,
+        atpress,
+        attemp,
+    ))
+}
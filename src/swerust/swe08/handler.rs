@@ -1,7 +1,8 @@
 use crate::raw;
-use crate::sweconst::Calandar;
-use std::ffi::{CStr, CString};
-// use std::os::raw::c_char;
+use crate::sweconst::{Bodies, Calandar, CalcFlags, Weekday};
+use crate::sweerror::{self, Call, SweError};
+use num_traits::FromPrimitive;
+use std::os::raw::c_char;
 
 /*
  * 8. Date and time conversion functions
@@ -81,9 +82,8 @@ pub fn utc_time_zone(
 pub struct UtcToJdResult {
     pub julian_day_et: f64,
     pub julian_day_ut: f64,
-    pub err: String, // To do in other file same struct
-    pub result: i32,
 }
+
 pub fn utc_to_jd(
     year: i32,
     month: i32,
@@ -92,13 +92,11 @@ pub fn utc_to_jd(
     min: i32,
     sec: f64,
     calandar: Calandar,
-) -> UtcToJdResult {
+) -> Result<UtcToJdResult, SweError> {
     let mut dret = [0.0; 2];
-    let mut serr = [0; 255];
-    unsafe {
-        let p_dret = dret.as_mut_ptr();
-        let p_serr = serr.as_mut_ptr();
-        let result = raw::swe_utc_to_jd(
+    let mut serr = [0; sweerror::SERR_LEN];
+    let result = unsafe {
+        raw::swe_utc_to_jd(
             year,
             month,
             day,
@@ -106,18 +104,376 @@ pub fn utc_to_jd(
             min,
             sec,
             calandar as i32,
-            p_dret,
-            p_serr,
+            dret.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(result, &serr, Call::Date)?;
+    Ok(UtcToJdResult {
+        julian_day_et: dret[0],
+        julian_day_ut: dret[1],
+    })
+}
+
+/// Julian day of a date, Err(SweError::InvalidDate) if the date is not
+/// valid in the calandar (31 February, month 13...)
+pub fn date_conversion(
+    year: i32,
+    month: i32,
+    day: i32,
+    hour: f64,
+    calandar: Calandar,
+) -> Result<f64, SweError> {
+    let c = match calandar {
+        Calandar::Julian => b'j',
+        Calandar::Gregorian => b'g',
+    };
+    let mut tjd = [0.0; 1];
+    let status = unsafe {
+        raw::swe_date_conversion(
+            year,
+            month,
+            day,
+            hour,
+            c as c_char,
+            tjd.as_mut_ptr(),
+        )
+    };
+    if status < 0 {
+        return Err(SweError::InvalidDate(format!(
+            "invalid date {}-{:02}-{:02} {}",
+            year, month, day, hour
+        )));
+    }
+    Ok(tjd[0])
+}
+
+/// Date of a julian day (inverse of julday)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RevjulResult {
+    pub year: i32,
+    pub month: i32,
+    pub day: i32,
+    /// Decimal hour
+    pub hour: f64,
+}
+
+/// Date of a julian day (inverse of julday)
+pub fn revjul(tjd: f64, calandar: Calandar) -> RevjulResult {
+    let mut year = [0; 1];
+    let mut month = [0; 1];
+    let mut day = [0; 1];
+    let mut hour = [0.0; 1];
+    unsafe {
+        raw::swe_revjul(
+            tjd,
+            calandar as i32,
+            year.as_mut_ptr(),
+            month.as_mut_ptr(),
+            day.as_mut_ptr(),
+            hour.as_mut_ptr(),
+        )
+    };
+    RevjulResult {
+        year: year[0],
+        month: month[0],
+        day: day[0],
+        hour: hour[0],
+    }
+}
+
+/// Day of the week of a julian day
+pub fn day_of_week(tjd: f64) -> Weekday {
+    let day = unsafe { raw::swe_day_of_week(tjd) };
+    Weekday::from_i32(day).unwrap_or(Weekday::Monday)
+}
+
+/// Date and time in UTC (inverse of utc_to_jd)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UtcDateTime {
+    pub year: i32,
+    pub month: i32,
+    pub day: i32,
+    pub hour: i32,
+    pub min: i32,
+    /// Decimal second (60.x during a leap second)
+    pub sec: f64,
+}
+
+/// Date and time in UTC of a julian day in ET/TT
+pub fn jdet_to_utc(tjd_et: f64, calandar: Calandar) -> UtcDateTime {
+    jd_to_utc(raw::swe_jdet_to_utc, tjd_et, calandar)
+}
+
+/// Date and time in UTC of a julian day in UT (UT1)
+pub fn jdut1_to_utc(tjd_ut: f64, calandar: Calandar) -> UtcDateTime {
+    jd_to_utc(raw::swe_jdut1_to_utc, tjd_ut, calandar)
+}
+
+type JdToUtcFn = unsafe extern "C" fn(
+    f64,
+    i32,
+    *mut i32,
+    *mut i32,
+    *mut i32,
+    *mut i32,
+    *mut i32,
+    *mut f64,
+);
+
+fn jd_to_utc(f: JdToUtcFn, tjd: f64, calandar: Calandar) -> UtcDateTime {
+    let mut year = [0; 1];
+    let mut month = [0; 1];
+    let mut day = [0; 1];
+    let mut hour = [0; 1];
+    let mut min = [0; 1];
+    let mut sec = [0.0; 1];
+    unsafe {
+        f(
+            tjd,
+            calandar as i32,
+            year.as_mut_ptr(),
+            month.as_mut_ptr(),
+            day.as_mut_ptr(),
+            hour.as_mut_ptr(),
+            min.as_mut_ptr(),
+            sec.as_mut_ptr(),
+        )
+    };
+    UtcDateTime {
+        year: year[0],
+        month: month[0],
+        day: day[0],
+        hour: hour[0],
+        min: min[0],
+        sec: sec[0],
+    }
+}
+
+/// Equation of time in days (E = LAT - LMT, local apparent time minus local
+/// mean time) at the julian day in UT
+pub fn time_equ(tjd_ut: f64) -> Result<f64, SweError> {
+    let mut e = [0.0; 1];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status =
+        unsafe { raw::swe_time_equ(tjd_ut, e.as_mut_ptr(), serr.as_mut_ptr()) };
+    sweerror::check_status(
+        status,
+        &serr,
+        Call::Body {
+            ipl: Bodies::Sun as i32,
+            tjd: tjd_ut,
+            iflag: 0,
+        },
+    )?;
+    Ok(e[0])
+}
+
+/// Local Mean Time to Local Apparent Time (sundial time)
+///
+/// geolon: eastern longitudes are positive, western negative
+pub fn lmt_to_lat(tjd_lmt: f64, geolon: f64) -> Result<f64, SweError> {
+    let mut tjd_lat = [0.0; 1];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_lmt_to_lat(
+            tjd_lmt,
+            geolon,
+            tjd_lat.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(
+        status,
+        &serr,
+        Call::Body {
+            ipl: Bodies::Sun as i32,
+            tjd: tjd_lmt,
+            iflag: 0,
+        },
+    )?;
+    Ok(tjd_lat[0])
+}
+
+/// Local Apparent Time (sundial time) to Local Mean Time
+///
+/// geolon: eastern longitudes are positive, western negative
+pub fn lat_to_lmt(tjd_lat: f64, geolon: f64) -> Result<f64, SweError> {
+    let mut tjd_lmt = [0.0; 1];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_lat_to_lmt(
+            tjd_lat,
+            geolon,
+            tjd_lmt.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(
+        status,
+        &serr,
+        Call::Body {
+            ipl: Bodies::Sun as i32,
+            tjd: tjd_lat,
+            iflag: 0,
+        },
+    )?;
+    Ok(tjd_lmt[0])
+}
+
+/// Julian day in UT of a julian day in Local Mean Time
+pub fn lmt_to_ut(tjd_lmt: f64, geolon: f64) -> f64 {
+    tjd_lmt - geolon / 360.0
+}
+
+/// Julian day in UT of a julian day in Local Apparent Time (sundial time)
+pub fn lat_to_ut(tjd_lat: f64, geolon: f64) -> Result<f64, SweError> {
+    Ok(lmt_to_ut(lat_to_lmt(tjd_lat, geolon)?, geolon))
+}
+
+/*
+ * 9. Delta T-related functions
+ */
+
+/// Delta T in days (tjd_et = tjd_ut + deltat), tidal acceleration of the
+/// last ephemeris used
+pub fn deltat(tjd: f64) -> f64 {
+    unsafe { raw::swe_deltat(tjd) }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeltatResult {
+    /// Delta T in days
+    pub deltat: f64,
+    /// Some(SweError::MoshierFallback) if the file of the ephemeris asked
+    /// in iflag has not been found, delta T is then computed with the tidal
+    /// acceleration of Moshier
+    pub warning: Option<SweError>,
+}
+
+/// Delta T in days with the tidal acceleration of the ephemeris in iflag
+/// (JplEph, SwissEph or Moshier)
+pub fn deltat_ex(tjd: f64, iflag: CalcFlags) -> Result<DeltatResult, SweError> {
+    let iflag = iflag.validate()?.bits();
+    let mut serr = [0; sweerror::SERR_LEN];
+    let deltat = unsafe { raw::swe_deltat_ex(tjd, iflag, serr.as_mut_ptr()) };
+    let serr = sweerror::serr_to_string(&serr);
+    Ok(DeltatResult {
+        deltat,
+        warning: if serr.is_empty() {
+            None
+        } else {
+            Some(SweError::MoshierFallback(serr))
+        },
+    })
+}
+
+/// Set a user defined delta T in days for deltat and deltat_ex
+/// None: delta T computed by the c library (SE_DELTAT_AUTOMATIC)
+pub fn set_delta_t_userdef(dt: Option<f64>) {
+    unsafe { raw::swe_set_delta_t_userdef(dt.unwrap_or(-1E-10)) }
+}
+
+/// Tidal acceleration of the Moon in arcsec/cty^2 used by deltat
+pub fn get_tid_acc() -> f64 {
+    unsafe { raw::swe_get_tid_acc() }
+}
+
+/// Set the tidal acceleration of the Moon in arcsec/cty^2 (-25.80 DE431)
+/// None: tidal acceleration of the ephemeris used (SE_TIDAL_AUTOMATIC)
+pub fn set_tid_acc(t_acc: Option<f64>) {
+    unsafe { raw::swe_set_tid_acc(t_acc.unwrap_or(999999.0)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const J2000: f64 = 2_451_545.0;
+
+    #[test]
+    fn date_conversion_valid_date() {
+        assert_eq!(
+            date_conversion(2000, 1, 1, 12.0, Calandar::Gregorian),
+            Ok(J2000)
         );
-        let s_serr = CString::from(CStr::from_ptr(p_serr))
-            .to_str()
-            .unwrap()
-            .to_string();
-        UtcToJdResult {
-            julian_day_et: dret[0],
-            julian_day_ut: dret[1],
-            err: s_serr,
-            result,
+        assert!(date_conversion(2020, 2, 29, 0.0, Calandar::Gregorian).is_ok());
+        // 1900 is a leap year only in the julian calandar
+        assert!(date_conversion(1900, 2, 29, 0.0, Calandar::Julian).is_ok());
+    }
+
+    #[test]
+    fn date_conversion_invalid_date() {
+        for (year, month, day) in
+            [(2021, 2, 31), (2021, 2, 29), (1900, 2, 29), (2021, 13, 1)].iter()
+        {
+            assert!(matches!(
+                date_conversion(*year, *month, *day, 0.0, Calandar::Gregorian),
+                Err(SweError::InvalidDate(_))
+            ));
         }
     }
+
+    #[test]
+    fn revjul_j2000() {
+        assert_eq!(
+            revjul(J2000 + 0.25, Calandar::Gregorian),
+            RevjulResult {
+                year: 2000,
+                month: 1,
+                day: 1,
+                hour: 18.0
+            }
+        );
+        // Last day of the julian calandar
+        let date = revjul(
+            julday(1582, 10, 15, 0.0, Calandar::Gregorian),
+            Calandar::Julian,
+        );
+        assert_eq!((date.year, date.month, date.day), (1582, 10, 5));
+    }
+
+    #[test]
+    fn day_of_week_j2000() {
+        assert_eq!(day_of_week(J2000), Weekday::Saturday);
+        assert_eq!(day_of_week(J2000 + 2.0), Weekday::Monday);
+    }
+
+    #[test]
+    fn jd_to_utc_j2000() {
+        // TT - UTC = 32 leap seconds + 32.184 s
+        let utc = jdet_to_utc(J2000, Calandar::Gregorian);
+        assert_eq!((utc.year, utc.month, utc.day), (2000, 1, 1));
+        assert_eq!((utc.hour, utc.min), (11, 58));
+        assert!((utc.sec - 55.816).abs() < 1e-3);
+        // UT1 - UTC is under 0.9 s
+        let utc = jdut1_to_utc(J2000, Calandar::Gregorian);
+        assert_eq!((utc.hour, utc.min), (11, 59));
+        assert!(utc.sec > 59.1);
+        let jd = utc_to_jd(
+            utc.year,
+            utc.month,
+            utc.day,
+            utc.hour,
+            utc.min,
+            utc.sec,
+            Calandar::Gregorian,
+        )
+        .unwrap();
+        assert!((jd.julian_day_ut - J2000).abs() < 1e-8);
+    }
+
+    #[test]
+    fn deltat_ex_moshier_fallback() {
+        use crate::sweconst::OptionalFlag;
+        // No ephemeris file in the repository
+        let swisseph = deltat_ex(J2000, OptionalFlag::SwissEph.into()).unwrap();
+        let moshier = deltat_ex(J2000, OptionalFlag::Moshier.into()).unwrap();
+        assert!(matches!(
+            swisseph.warning,
+            Some(SweError::MoshierFallback(_))
+        ));
+        assert_eq!(moshier.warning, None);
+        assert_eq!(swisseph.deltat, moshier.deltat);
+    }
 }
@@ -0,0 +1,11 @@
+use crate::raw;
+use crate::sweconst::GeoPosition;
+
+/*
+ * 10. The function swe_set_topo() for topocentric planet positions
+ */
+
+/// Set the observer for OptionalFlag::TopocentricPosition
+pub fn set_topo(geo: GeoPosition) {
+    unsafe { raw::swe_set_topo(geo.longitude, geo.latitude, geo.altitude) }
+}
@@ -0,0 +1,83 @@
+use crate::raw;
+use crate::sweconst::{Ayanamsa, Bodies, CalcFlags};
+use crate::sweerror::{self, Call, SweError};
+use std::ffi::CStr;
+
+/*
+ * 11. Sidereal mode functions
+ */
+
+/// Set the ayanamsa used with OptionalFlag::SideralPosition
+///
+/// t0 (julian day TT) and ayan_t0 (ayanamsa at t0) are only used with
+/// Ayanamsa::User
+pub fn set_sid_mode(ayanamsa: Ayanamsa, t0: f64, ayan_t0: f64) {
+    unsafe { raw::swe_set_sid_mode(ayanamsa as i32, t0, ayan_t0) }
+}
+
+/// Ayanamsa (set with set_sid_mode) at the julian day in UT
+///
+/// iflag: ephemeris (JplEph, SwissEph, Moshier) and NoNutation
+pub fn get_ayanamsa_ex_ut(
+    tjd_ut: f64,
+    iflag: CalcFlags,
+) -> Result<f64, SweError> {
+    let iflag = iflag.validate()?.bits();
+    let mut daya = [0.0; 1];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_get_ayanamsa_ex_ut(
+            tjd_ut,
+            iflag,
+            daya.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(
+        status,
+        &serr,
+        Call::Body {
+            ipl: Bodies::EclNut as i32,
+            tjd: tjd_ut,
+            iflag,
+        },
+    )?;
+    Ok(daya[0])
+}
+
+/// Ayanamsa (set with set_sid_mode) at the julian day in ET/TT
+pub fn get_ayanamsa_ex(tjd_et: f64, iflag: CalcFlags) -> Result<f64, SweError> {
+    let iflag = iflag.validate()?.bits();
+    let mut daya = [0.0; 1];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_get_ayanamsa_ex(
+            tjd_et,
+            iflag,
+            daya.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(
+        status,
+        &serr,
+        Call::Body {
+            ipl: Bodies::EclNut as i32,
+            tjd: tjd_et,
+            iflag,
+        },
+    )?;
+    Ok(daya[0])
+}
+
+/// Name of the ayanamsa in the c library (no name for Ayanamsa::User)
+pub fn get_ayanamsa_name(ayanamsa: Ayanamsa) -> Result<String, SweError> {
+    let name = unsafe { raw::swe_get_ayanamsa_name(ayanamsa as i32) };
+    if name.is_null() {
+        return Err(SweError::Calculation(format!(
+            "no name for the ayanamsa {}",
+            ayanamsa
+        )));
+    }
+    sweerror::c_str_to_string(unsafe { CStr::from_ptr(name) })
+}
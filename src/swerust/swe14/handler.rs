@@ -1,36 +1,145 @@
 use crate::raw;
-// use crate::sweconst::HouseSystem;
-use std::ffi::{CStr, CString};
+use crate::sweconst::{Angle, CalcFlags, House, HouseSystem, OptionalFlag};
+use crate::sweerror::{self, SweError};
+use std::f64::consts::PI;
+use std::ffi::CStr;
 use std::os::raw::c_int;
 
 /*
  * 14. House cusp calculation
  */
 
-pub fn house_name(hsys: char) -> String {
-    unsafe {
-        CString::from(CStr::from_ptr(raw::swe_house_name(hsys as c_int)))
-            .to_str()
-            .unwrap()
-            .to_string()
-    }
+/// Difference under which a cusp is on an angle (the c library copies the
+/// ascendant and the MC in the cusps for most systems)
+const ANGLE_PRECISION: f64 = 1e-9;
+
+/// Name of the house system in the c library ("Placidus", "equal/ whole
+/// sign"...)
+pub fn house_name(hsys: HouseSystem) -> Result<String, SweError> {
+    sweerror::c_str_to_string(unsafe {
+        CStr::from_ptr(raw::swe_house_name(hsys.hsys() as c_int))
+    })
 }
 
+/// Cusps and angles of swe_houses_ex and swe_houses_armc
 #[derive(Debug, Clone)]
 pub struct HousesResult {
-    // cusps: [f64; 37], // Limtation to 32 ->
-    // /* array for 13 (or 37 for system G) doubles */
+    /// Cusps of the houses 1 to 12 (sectors 1 to 36 for Gauquelin)
     pub cusps: Vec<f64>,
-    pub ascmc: [f64; 10],
-    pub result: i32,
+    pub ascendant: f64,
+    pub mc: f64,
+    /// Sidereal time in degrees
+    pub armc: f64,
+    pub vertex: f64,
+    /// Equatorial ascendant
+    pub equasc: f64,
+    /// Co-ascendant (Walter Koch)
+    pub coasc1: f64,
+    /// Co-ascendant (Michael Munkasey)
+    pub coasc2: f64,
+    /// Polar ascendant (Michael Munkasey)
+    pub polasc: f64,
+    /// Some(SweError::HouseSystem) if the house system can't be computed at
+    /// this latitude (polar circle), the cusps are then the Porphyry cusps
+    pub warning: Option<SweError>,
+    /// Angles in radians (OptionalFlag::Radians), in degrees otherwise
+    pub radians: bool,
+}
+
+impl HousesResult {
+    /// cusps and ascmc as filled by the c library (cusps[0] unused), status
+    /// returned by the c library
+    fn new(
+        hsys: HouseSystem,
+        geolat: f64,
+        status: i32,
+        cusps: &[f64; 37],
+        ascmc: &[f64; 10],
+        radians: bool,
+    ) -> HousesResult {
+        let warning = if status < 0 {
+            Some(SweError::HouseSystem {
+                hsys: hsys.hsys(),
+                geolat,
+            })
+        } else {
+            None
+        };
+        let half_circle = if radians { PI } else { 180.0 };
+        // The c library has switched to Porphyry (12 cusps) on error
+        let cusps = if hsys != HouseSystem::Gauquelin {
+            cusps[1..=12].to_vec()
+        } else if warning.is_none() {
+            cusps[1..=36].to_vec()
+        } else {
+            // Only the cusps 2-3 and 11-12 are filled for the Gauquelin
+            // sectors, the cusps 1 and 10 are the ascendant and the MC, the
+            // others are opposite
+            let mut porphyry = cusps[1..=12].to_vec();
+            porphyry[0] = ascmc[0];
+            porphyry[9] = ascmc[1];
+            for i in 3..9 {
+                porphyry[i] = (porphyry[(i + 6) % 12] + half_circle)
+                    % (2.0 * half_circle);
+            }
+            porphyry
+        };
+        HousesResult {
+            cusps,
+            ascendant: ascmc[0],
+            mc: ascmc[1],
+            armc: ascmc[2],
+            vertex: ascmc[3],
+            equasc: ascmc[4],
+            coasc1: ascmc[5],
+            coasc2: ascmc[6],
+            polasc: ascmc[7],
+            warning,
+            radians,
+        }
+    }
+
+    /// Houses numbered from 1, with the angle on their cusp (not all the
+    /// systems have the ascendant and the MC on a cusp: equal, whole sign...)
+    pub fn houses(&self) -> Vec<House> {
+        self.cusps
+            .iter()
+            .enumerate()
+            .map(|(i, cusp)| House::new(i as i32 + 1, *cusp, self.angle(*cusp)))
+            .collect()
+    }
+
+    fn angle(&self, cusp: f64) -> Angle {
+        let half_circle = if self.radians { PI } else { 180.0 };
+        let is_on = |point: f64| {
+            let diff = (cusp - point).abs() % (2.0 * half_circle);
+            diff.min(2.0 * half_circle - diff) < ANGLE_PRECISION
+        };
+        if is_on(self.ascendant) {
+            Angle::Asc
+        } else if is_on(self.ascendant + half_circle) {
+            Angle::Desc
+        } else if is_on(self.mc) {
+            Angle::Mc
+        } else if is_on(self.mc + half_circle) {
+            Angle::Fc
+        } else {
+            Angle::Nothing
+        }
+    }
 }
 
+/// iflag: only SideralPosition, Radians and NoNutation are used
+/// In the polar circle, the systems who can't be computed there (Placidus,
+/// Koch, Gauquelin...) give the Porphyry cusps with a warning
 pub fn houses(
     tjd_ut: f64,
     geolat: f64,
     geolong: f64,
-    hsys: char,
-) -> HousesResult {
+    hsys: HouseSystem,
+    iflag: CalcFlags,
+) -> Result<HousesResult, SweError> {
+    let iflag = iflag.validate()?.bits();
     let mut cusps = [0.0; 37];
     let mut ascmc = [0.0; 10];
     let result: i32 = unsafe {
@@ -38,17 +147,185 @@ pub fn houses(
         let p_ascmc = ascmc.as_mut_ptr();
         raw::swe_houses_ex(
             tjd_ut,
-            0, // 64 | (64 * 1024),
+            iflag,
             geolat,
             geolong,
-            hsys as c_int,
+            hsys.hsys() as c_int,
             p_cuspsw,
             p_ascmc,
         )
     };
-    HousesResult {
-        cusps: cusps.to_vec(),
-        ascmc,
+    Ok(HousesResult::new(
+        hsys,
+        geolat,
         result,
+        &cusps,
+        &ascmc,
+        iflag & OptionalFlag::Radians as i32 != 0,
+    ))
+}
+
+/// Houses from the ARMC (sidereal time in degrees), without a date
+/// (composite charts, progressed angles...)
+///
+/// armc, geolat and eps (obliquity of the ecliptic) in degrees
+/// The Sunshine houses use the declination of the Sun of the last call to
+/// houses (0 before)
+/// In the polar circle, Porphyry cusps with a warning, like houses
+pub fn houses_armc(
+    armc: f64,
+    geolat: f64,
+    eps: f64,
+    hsys: HouseSystem,
+) -> Result<HousesResult, SweError> {
+    let mut cusps = [0.0; 37];
+    let mut ascmc = [0.0; 10];
+    // No declination of the Sun given for the Sunshine houses
+    ascmc[9] = 99.0;
+    let result: i32 = unsafe {
+        raw::swe_houses_armc(
+            armc,
+            geolat,
+            eps,
+            hsys.hsys() as c_int,
+            cusps.as_mut_ptr(),
+            ascmc.as_mut_ptr(),
+        )
+    };
+    Ok(HousesResult::new(
+        hsys, geolat, result, &cusps, &ascmc, false,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const J2000: f64 = 2_451_545.0;
+
+    /// Houses numbered from 1 with an angle on their cusp
+    fn angles(result: &HousesResult) -> Vec<(i32, Angle)> {
+        result
+            .houses()
+            .into_iter()
+            .filter(|house| house.angle != Angle::Nothing)
+            .map(|house| (house.object_id, house.angle))
+            .collect()
+    }
+
+    #[test]
+    fn houses_angles_placidus() {
+        let result =
+            houses(J2000, 46.2, 6.15, HouseSystem::Placidus, CalcFlags::new())
+                .unwrap();
+        assert!(result.warning.is_none());
+        assert_eq!(
+            angles(&result),
+            vec![
+                (1, Angle::Asc),
+                (4, Angle::Fc),
+                (7, Angle::Desc),
+                (10, Angle::Mc)
+            ]
+        );
+    }
+
+    #[test]
+    fn houses_angles_equal() {
+        let result =
+            houses(J2000, 46.2, 6.15, HouseSystem::Equal, CalcFlags::new())
+                .unwrap();
+        assert_eq!(angles(&result), vec![(1, Angle::Asc), (7, Angle::Desc)]);
+    }
+
+    #[test]
+    fn houses_angles_whole_sign() {
+        let result =
+            houses(J2000, 46.2, 6.15, HouseSystem::WholeSign, CalcFlags::new())
+                .unwrap();
+        assert_eq!(angles(&result), vec![]);
+    }
+
+    #[test]
+    fn houses_angles_gauquelin() {
+        let result =
+            houses(J2000, 46.2, 6.15, HouseSystem::Gauquelin, CalcFlags::new())
+                .unwrap();
+        assert_eq!(result.cusps.len(), 36);
+        assert_eq!(
+            angles(&result),
+            vec![
+                (1, Angle::Asc),
+                (10, Angle::Mc),
+                (19, Angle::Desc),
+                (28, Angle::Fc)
+            ]
+        );
+    }
+
+    #[test]
+    fn houses_angles_radians() {
+        let iflag = CalcFlags::from(OptionalFlag::Radians);
+        let result =
+            houses(J2000, 46.2, 6.15, HouseSystem::Placidus, iflag).unwrap();
+        assert!(result.ascendant < 2.0 * PI);
+        assert_eq!(
+            angles(&result),
+            vec![
+                (1, Angle::Asc),
+                (4, Angle::Fc),
+                (7, Angle::Desc),
+                (10, Angle::Mc)
+            ]
+        );
+    }
+
+    #[test]
+    fn houses_angles_polar_circle() {
+        let result =
+            houses(J2000, 70.0, 25.0, HouseSystem::Placidus, CalcFlags::new())
+                .unwrap();
+        assert!(matches!(
+            result.warning,
+            Some(SweError::HouseSystem { hsys: 'P', .. })
+        ));
+        assert_eq!(
+            angles(&result),
+            vec![
+                (1, Angle::Asc),
+                (4, Angle::Fc),
+                (7, Angle::Desc),
+                (10, Angle::Mc)
+            ]
+        );
+    }
+
+    #[test]
+    fn houses_gauquelin_polar_circle() {
+        let result =
+            houses(J2000, 70.0, 25.0, HouseSystem::Gauquelin, CalcFlags::new())
+                .unwrap();
+        let porphyry = houses(
+            J2000,
+            70.0,
+            25.0,
+            HouseSystem::Porphyrius,
+            CalcFlags::new(),
+        )
+        .unwrap();
+        assert!(result.warning.is_some());
+        assert_eq!(result.cusps.len(), 12);
+        for (cusp, expected) in result.cusps.iter().zip(porphyry.cusps.iter()) {
+            assert!((cusp - expected).abs() < 1e-9);
+        }
+        assert_eq!(
+            angles(&result),
+            vec![
+                (1, Angle::Asc),
+                (4, Angle::Fc),
+                (7, Angle::Desc),
+                (10, Angle::Mc)
+            ]
+        );
     }
 }
@@ -0,0 +1,227 @@
+use crate::raw;
+use crate::sweconst::{
+    Bodies, BodyOrStar, CalcFlags, GauquelinMethod, GeoPosition, HouseSystem,
+    Object, OptionalFlag,
+};
+use crate::sweerror::{self, Call, SweError};
+use crate::swerust::{handler_swe03, handler_swe11, handler_swe16};
+use std::os::raw::c_int;
+use std::ptr;
+
+/*
+ * 15. House position of a planet
+ *
+ * The house position is a fractional number: 1.0 to 12.999999 (1.0 to
+ * 36.999999 for the Gauquelin sectors, numbered clockwise), the integer part
+ * is the house and the fractional part the distance from its cusp
+ * ("Mars in 7th house" for 7.42)
+ */
+
+/// House position of a point in tropical ecliptic coordinates
+///
+/// armc, geolat and eps (obliquity of the ecliptic) in degrees
+/// Err if the position can't be computed (Koch houses with a circumpolar
+/// planet or MC)
+pub fn house_pos(
+    armc: f64,
+    geolat: f64,
+    eps: f64,
+    hsys: HouseSystem,
+    longitude: f64,
+    latitude: f64,
+) -> Result<f64, SweError> {
+    let mut xpin = [longitude, latitude];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let hpos = unsafe {
+        raw::swe_house_pos(
+            armc,
+            geolat,
+            eps,
+            hsys.hsys() as c_int,
+            xpin.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    if hpos == 0.0 {
+        return Err(Call::Other.error(sweerror::serr_to_string(&serr)));
+    }
+    Ok(hpos)
+}
+
+/// House position of each object at the julian day in UT, with its
+/// ecliptic latitude
+///
+/// The ARMC and the true obliquity are computed for tjd_ut
+/// iflag: the ephemeris and SideralPosition if the objects are sidereal
+/// (the ayanamsa is added back, swe_house_pos needs tropical positions)
+pub fn objects_house_pos(
+    tjd_ut: f64,
+    geolat: f64,
+    geolong: f64,
+    hsys: HouseSystem,
+    iflag: CalcFlags,
+    objects: &[Object],
+) -> Result<Vec<f64>, SweError> {
+    let iflag_ephe = iflag.ephemeris();
+    let armc = handler_swe16::armc(tjd_ut, geolong);
+    let eps =
+        handler_swe03::calc_ut(tjd_ut, Bodies::EclNut, iflag_ephe)?.longitude;
+    let ayanamsa = if iflag.contains(OptionalFlag::SideralPosition) {
+        handler_swe11::get_ayanamsa_ex_ut(tjd_ut, iflag_ephe)?
+    } else {
+        0.0
+    };
+    objects
+        .iter()
+        .map(|o| {
+            house_pos(
+                armc,
+                geolat,
+                eps,
+                hsys,
+                o.longitude + ayanamsa,
+                o.latitude,
+            )
+        })
+        .collect()
+}
+
+/// Gauquelin sector (1.0 to 36.999999, numbered clockwise) of a body or a
+/// fixed star at the julian day in UT
+///
+/// iflag: the ephemeris and TopocentricPosition
+/// atpress (0 for 1013.25 mbar) and attemp are only used by the methods
+/// with refraction
+/// Err for the methods from rising and setting if the body is circumpolar
+/// With a method from rising and setting, swe_set_topo is called (see
+/// Ephemeris::gauquelin_sector)
+#[allow(clippy::too_many_arguments)]
+pub fn gauquelin_sector(
+    tjd_ut: f64,
+    body: &BodyOrStar,
+    iflag: CalcFlags,
+    imeth: GauquelinMethod,
+    geo: GeoPosition,
+    atpress: f64,
+    attemp: f64,
+) -> Result<f64, SweError> {
+    let iflag = iflag.validate()?.bits();
+    let ipl = body.ipl()?;
+    let mut star = body.star_buf()?;
+    let mut geopos = geo.geopos();
+    let mut dgsect = [0.0; 1];
+    let mut serr = [0; sweerror::SERR_LEN];
+    let status = unsafe {
+        raw::swe_gauquelin_sector(
+            tjd_ut,
+            ipl,
+            star.as_mut().map_or(ptr::null_mut(), |s| s.as_mut_ptr()),
+            iflag,
+            imeth as i32,
+            geopos.as_mut_ptr(),
+            atpress,
+            attemp,
+            dgsect.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    sweerror::check_status(status, &serr, body.call(tjd_ut, iflag))?;
+    Ok(dgsect[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweconst::ObjectType;
+    use crate::swerust::handler_swe14;
+
+    /// J2000 + 7h, Geneva
+    const TJD_UT: f64 = 2_451_545.0 + 7.0 / 24.0;
+    const GEOLAT: f64 = 46.2;
+    const GEOLONG: f64 = 6.15;
+
+    fn houses(hsys: HouseSystem) -> handler_swe14::HousesResult {
+        handler_swe14::houses(TJD_UT, GEOLAT, GEOLONG, hsys, CalcFlags::new())
+            .unwrap()
+    }
+
+    fn eps() -> f64 {
+        handler_swe03::calc_ut(TJD_UT, Bodies::EclNut, CalcFlags::new())
+            .unwrap()
+            .longitude
+    }
+
+    #[test]
+    fn house_pos_mars_in_7th() {
+        let houses = houses(HouseSystem::Placidus);
+        let mars =
+            handler_swe03::calc_ut(TJD_UT, Bodies::Mars, CalcFlags::new())
+                .unwrap();
+        let hpos = house_pos(
+            houses.armc,
+            GEOLAT,
+            eps(),
+            HouseSystem::Placidus,
+            mars.longitude,
+            mars.latitude,
+        )
+        .unwrap();
+        assert_eq!(hpos.floor(), 7.0);
+        let cusps = &houses.cusps;
+        assert!(cusps[6] < mars.longitude && mars.longitude < cusps[7]);
+        let objects = [Object::new(
+            Bodies::Mars,
+            "Mars",
+            ObjectType::PlanetOrStar,
+            mars.longitude,
+            mars.latitude,
+            mars.speed_longitude,
+        )];
+        let hpos_objects = objects_house_pos(
+            TJD_UT,
+            GEOLAT,
+            GEOLONG,
+            HouseSystem::Placidus,
+            CalcFlags::new(),
+            &objects,
+        )
+        .unwrap();
+        assert!((hpos_objects[0] - hpos).abs() < 1e-9);
+    }
+
+    #[test]
+    fn house_pos_on_cusps() {
+        let houses = houses(HouseSystem::Placidus);
+        let eps = eps();
+        for (i, cusp) in houses.cusps.iter().enumerate() {
+            let hpos = house_pos(
+                houses.armc,
+                GEOLAT,
+                eps,
+                HouseSystem::Placidus,
+                *cusp,
+                0.0,
+            )
+            .unwrap();
+            // 12.999999 on the ascendant
+            let diff = (hpos - (i + 1) as f64 + 6.0).rem_euclid(12.0) - 6.0;
+            assert!(diff.abs() < 1e-5, "cusp {}: {}", i + 1, hpos);
+        }
+    }
+
+    #[test]
+    fn house_pos_equal_middle_of_house() {
+        let houses = houses(HouseSystem::Equal);
+        let longitude = (houses.ascendant + 6.0 * 30.0 + 15.0) % 360.0;
+        let hpos = house_pos(
+            houses.armc,
+            GEOLAT,
+            eps(),
+            HouseSystem::Equal,
+            longitude,
+            0.0,
+        )
+        .unwrap();
+        assert!((hpos - 7.5).abs() < 1e-6);
+    }
+}
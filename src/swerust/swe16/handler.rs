@@ -0,0 +1,116 @@
+use crate::raw;
+use crate::swerust::handler_swe17;
+
+/*
+ * 16. Sidereal time
+ *
+ * The sidereal time is in hours, the ARMC (right ascension of the MC, used by
+ * the houses) is the local sidereal time in degrees
+ */
+
+/// Length of the sidereal day in days of mean solar time
+const SIDEREAL_DAY: f64 = 0.997_269_566_3;
+
+/// Greenwich sidereal time in hours at the julian day in UT
+pub fn sidtime(tjd_ut: f64) -> f64 {
+    unsafe { raw::swe_sidtime(tjd_ut) }
+}
+
+/// Greenwich sidereal time in hours, with the obliquity of the ecliptic and
+/// the nutation in longitude given in degrees
+pub fn sidtime0(tjd_ut: f64, eps: f64, nut: f64) -> f64 {
+    unsafe { raw::swe_sidtime0(tjd_ut, eps, nut) }
+}
+
+/// Local sidereal time in hours at the geographic longitude (eastern
+/// longitudes are positive)
+pub fn local_sidtime(tjd_ut: f64, geolong: f64) -> f64 {
+    armc(tjd_ut, geolong) / 15.0
+}
+
+/// ARMC in degrees at the geographic longitude, as in
+/// handler_swe14::HousesResult
+pub fn armc(tjd_ut: f64, geolong: f64) -> f64 {
+    handler_swe17::degnorm(sidtime(tjd_ut) * 15.0 + geolong)
+}
+
+/// First julian day in UT, at or after tjd_ut, when the local sidereal time
+/// at the geographic longitude is lst hours
+///
+/// A local sidereal time happens once a day, twice on the days where it
+/// happens in the first 4 minutes (the sidereal day is shorter)
+pub fn local_sidtime_to_ut(tjd_ut: f64, geolong: f64, lst: f64) -> f64 {
+    // Sidereal hours from the local sidereal time at t to lst
+    let lag = |t: f64| {
+        handler_swe17::degnorm((lst - local_sidtime(t, geolong)) * 15.0) / 15.0
+    };
+    let mut t = tjd_ut + lag(tjd_ut) / 24.0 * SIDEREAL_DAY;
+    // Nutation changes the sidereal time a little, refine around t
+    for _ in 0..3 {
+        let mut d = lag(t);
+        if d > 12.0 {
+            d -= 24.0;
+        }
+        t += d / 24.0 * SIDEREAL_DAY;
+    }
+    t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const J2000: f64 = 2_451_545.0;
+    /// 0.01 second of sidereal time in hours
+    const LST_PRECISION: f64 = 0.01 / 3600.0;
+
+    /// Difference of 2 sidereal times in hours, between -12 and 12
+    fn lst_diff(a: f64, b: f64) -> f64 {
+        handler_swe17::degnorm((a - b) * 15.0 + 180.0) / 15.0 - 12.0
+    }
+
+    #[test]
+    fn local_sidtime_to_ut_at_tjd_ut() {
+        let geolong = 6.15;
+        let lst = local_sidtime(J2000, geolong);
+        let t = local_sidtime_to_ut(J2000, geolong, lst);
+        assert!((t - J2000).abs() < 1e-6);
+    }
+
+    #[test]
+    fn local_sidtime_to_ut_just_before_tjd_ut() {
+        let geolong = 6.15;
+        let lst = local_sidtime(J2000, geolong) - 0.01;
+        let t = local_sidtime_to_ut(J2000, geolong, lst);
+        assert!(t >= J2000);
+        assert!(lst_diff(local_sidtime(t, geolong), lst).abs() < LST_PRECISION);
+        // Next sidereal day
+        assert!((t - J2000 - SIDEREAL_DAY).abs() < 0.001);
+    }
+
+    #[test]
+    fn local_sidtime_to_ut_matches_local_sidtime() {
+        let geolong = -73.97;
+        for lst in [0.0, 0.001, 6.5, 12.0, 18.25, 23.999].iter() {
+            let t = local_sidtime_to_ut(J2000, geolong, *lst);
+            assert!((J2000..J2000 + 1.0).contains(&t));
+            assert!(
+                lst_diff(local_sidtime(t, geolong), *lst).abs() < LST_PRECISION
+            );
+        }
+    }
+
+    #[test]
+    fn local_sidtime_to_ut_twice_a_day() {
+        let geolong = 0.0;
+        // 1 minute after the local sidereal time at J2000, the same local
+        // sidereal time comes back about 3 min 56 s before the end of the
+        // day
+        let lst = local_sidtime(J2000 + 1.0 / 1440.0, geolong);
+        let first = local_sidtime_to_ut(J2000, geolong, lst);
+        let second = local_sidtime_to_ut(first + 0.01, geolong, lst);
+        assert!((first - J2000 - 1.0 / 1440.0).abs() < 1e-6);
+        assert!(second < J2000 + 1.0);
+        assert!((second - first - SIDEREAL_DAY).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::raw;
+use crate::sweconst::{Bodies, CalcFlags, CoordinateSystem, GeoPosition};
+use crate::sweerror::SweError;
+use crate::swerust::handler_swe03::{self, CalcUtResult};
+use crate::swerust::horizon::{self, HorizonResult};
+
+/*
+ * 17. Coordinate transformations
+ *
+ * Ecliptic (longitude, latitude) <-> equatorial (right ascension,
+ * declination) with swe_cotrans and swe_cotrans_sp, in degrees. The
+ * conversions of a date use the true obliquity of the ecliptic
+ * (Bodies::EclNut), the horizontal coordinates are computed by the horizon
+ * module.
+ */
+
+/// Position and speed in ecliptic or equatorial coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinates {
+    /// Longitude, or right ascension in equatorial coordinates
+    pub longitude: f64,
+    /// Latitude, or declination in equatorial coordinates
+    pub latitude: f64,
+    /// Distance, unchanged by the transformations
+    pub distance: f64,
+    pub speed_longitude: f64,
+    pub speed_latitude: f64,
+    pub speed_distance: f64,
+}
+
+impl Coordinates {
+    /// Position without speed, at the distance 1.0
+    pub fn new(longitude: f64, latitude: f64) -> Coordinates {
+        Coordinates::from_xx([longitude, latitude, 1.0, 0.0, 0.0, 0.0])
+    }
+
+    /// Position and speed of handler_swe03::calc_ut
+    pub fn from_calc(calc: &CalcUtResult) -> Coordinates {
+        Coordinates::from_xx([
+            calc.longitude,
+            calc.latitude,
+            calc.distance_au,
+            calc.speed_longitude,
+            calc.speed_latitude,
+            calc.speed_distance_au,
+        ])
+    }
+
+    fn from_xx(xx: [f64; 6]) -> Coordinates {
+        Coordinates {
+            longitude: xx[0],
+            latitude: xx[1],
+            distance: xx[2],
+            speed_longitude: xx[3],
+            speed_latitude: xx[4],
+            speed_distance: xx[5],
+        }
+    }
+
+    fn xx(&self) -> [f64; 6] {
+        [
+            self.longitude,
+            self.latitude,
+            self.distance,
+            self.speed_longitude,
+            self.speed_latitude,
+            self.speed_distance,
+        ]
+    }
+}
+
+/// Transformation of the position (the speeds of the result are 0.0)
+///
+/// eps: obliquity of the ecliptic, positive from equatorial to ecliptic,
+/// negative from ecliptic to equatorial
+pub fn cotrans(pos: &Coordinates, eps: f64) -> Coordinates {
+    let mut xpo = [pos.longitude, pos.latitude, pos.distance];
+    let mut xpn = [0.0; 3];
+    unsafe { raw::swe_cotrans(xpo.as_mut_ptr(), xpn.as_mut_ptr(), eps) };
+    Coordinates::from_xx([xpn[0], xpn[1], xpn[2], 0.0, 0.0, 0.0])
+}
+
+/// Transformation of the position and the speed
+///
+/// eps: as cotrans
+pub fn cotrans_sp(pos: &Coordinates, eps: f64) -> Coordinates {
+    let mut xpo = pos.xx();
+    let mut xpn = [0.0; 6];
+    unsafe { raw::swe_cotrans_sp(xpo.as_mut_ptr(), xpn.as_mut_ptr(), eps) };
+    Coordinates::from_xx(xpn)
+}
+
+/// True obliquity of the ecliptic (with nutation) at the julian day in UT
+///
+/// iflag: the ephemeris (JplEph, SwissEph, Moshier)
+pub fn true_obliquity(tjd_ut: f64, iflag: CalcFlags) -> Result<f64, SweError> {
+    let iflag = iflag.ephemeris();
+    Ok(handler_swe03::calc_ut(tjd_ut, Bodies::EclNut, iflag)?.longitude)
+}
+
+/// Ecliptic of date to equatorial coordinates of date, with the speeds
+pub fn ecliptic_to_equatorial(
+    tjd_ut: f64,
+    iflag: CalcFlags,
+    pos: &Coordinates,
+) -> Result<Coordinates, SweError> {
+    Ok(cotrans_sp(pos, -true_obliquity(tjd_ut, iflag)?))
+}
+
+/// Equatorial coordinates of date to ecliptic of date, with the speeds
+pub fn equatorial_to_ecliptic(
+    tjd_ut: f64,
+    iflag: CalcFlags,
+    pos: &Coordinates,
+) -> Result<Coordinates, SweError> {
+    Ok(cotrans_sp(pos, true_obliquity(tjd_ut, iflag)?))
+}
+
+/// Horizontal coordinates of an ecliptic or equatorial position of date
+/// (see horizon::azalt)
+pub fn to_horizontal(
+    tjd_ut: f64,
+    coord: CoordinateSystem,
+    pos: &Coordinates,
+    geo: GeoPosition,
+    atpress: f64,
+    attemp: f64,
+) -> HorizonResult {
+    horizon::azalt(
+        tjd_ut,
+        coord,
+        geo,
+        atpress,
+        attemp,
+        pos.longitude,
+        pos.latitude,
+    )
+}
+
+/// Ecliptic or equatorial position of date of horizontal coordinates
+/// (see horizon::azalt_rev), without speed
+pub fn from_horizontal(
+    tjd_ut: f64,
+    coord: CoordinateSystem,
+    geo: GeoPosition,
+    azimuth: f64,
+    true_altitude: f64,
+) -> Coordinates {
+    let pos = horizon::azalt_rev(tjd_ut, coord, geo, azimuth, true_altitude);
+    Coordinates::new(pos.longitude, pos.latitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweconst::OptionalFlag;
+
+    const J2000: f64 = 2_451_545.0;
+
+    fn calc(ipl: Bodies, iflag: CalcFlags) -> CalcUtResult {
+        handler_swe03::calc_ut(J2000, ipl, iflag).unwrap()
+    }
+
+    #[test]
+    fn ecliptic_to_equatorial_as_calc_ut() {
+        let iflag = CalcFlags::from(OptionalFlag::Speed);
+        for ipl in [Bodies::Sun, Bodies::Moon, Bodies::Mars].iter() {
+            let ecl = Coordinates::from_calc(&calc(*ipl, iflag));
+            let equ = Coordinates::from_calc(&calc(
+                *ipl,
+                iflag | OptionalFlag::EquatorialPosition,
+            ));
+            let pos = ecliptic_to_equatorial(J2000, iflag, &ecl).unwrap();
+            assert!((pos.longitude - equ.longitude).abs() < 1e-10);
+            assert!((pos.latitude - equ.latitude).abs() < 1e-10);
+            assert!((pos.distance - equ.distance).abs() < 1e-10);
+            assert!((pos.speed_longitude - equ.speed_longitude).abs() < 1e-10);
+            assert!((pos.speed_latitude - equ.speed_latitude).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn equatorial_to_ecliptic_round_trip() {
+        let iflag = CalcFlags::from(OptionalFlag::Speed);
+        let ecl = Coordinates::from_calc(&calc(Bodies::Moon, iflag));
+        let equ = ecliptic_to_equatorial(J2000, iflag, &ecl).unwrap();
+        let pos = equatorial_to_ecliptic(J2000, iflag, &equ).unwrap();
+        assert!((pos.longitude - ecl.longitude).abs() < 1e-10);
+        assert!((pos.latitude - ecl.latitude).abs() < 1e-10);
+        assert!((pos.speed_longitude - ecl.speed_longitude).abs() < 1e-10);
+        assert!((pos.speed_latitude - ecl.speed_latitude).abs() < 1e-10);
+    }
+
+    #[test]
+    fn cotrans_without_speed() {
+        let pos = Coordinates::from_calc(&calc(
+            Bodies::Moon,
+            CalcFlags::from(OptionalFlag::Speed),
+        ));
+        let eps = true_obliquity(J2000, CalcFlags::new()).unwrap();
+        let with_speed = cotrans_sp(&pos, -eps);
+        let without_speed = cotrans(&pos, -eps);
+        assert_eq!(without_speed.longitude, with_speed.longitude);
+        assert_eq!(without_speed.latitude, with_speed.latitude);
+        assert_eq!(without_speed.speed_longitude, 0.0);
+        assert_eq!(without_speed.speed_latitude, 0.0);
+    }
+}
@@ -1 +1,2 @@
+pub mod coords;
 pub mod handler;